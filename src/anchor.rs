@@ -0,0 +1,360 @@
+//! Anchor and mooring – hold the ship still while the crew dives, or make fast at Safe Island.
+//!
+//! X drops the anchor to the seabed under the bow and pays out rode to the scope. The rode
+//! is a Rapier spring joint from the bow fairlead that only pulls once it comes taut; pull
+//! beyond the anchor's holding power drags it across the bottom (storm winds, sails left up).
+//! Off Safe Island, X instead snaps the ship onto the nearest mooring: a joint that pins her
+//! in place and heading but lets her ride the swell. X again weighs anchor or casts off.
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+use crate::character::MarineCharacter;
+use crate::game_state::GameState;
+use crate::hud::{spawn_hud_row, ANCHOR_SLOT};
+use crate::player::PlayerMode;
+use crate::settings::InputBindings;
+use crate::ship::Ship;
+use crate::world::{SPAWN_ISLAND_X, SPAWN_ISLAND_Z};
+
+/// Body-space bow fairlead the rode runs through (m).
+const FAIRLEAD: Vec3 = Vec3::new(0.0, 1.5, -9.5);
+
+/// Rode paid out per metre of depth.
+const SCOPE_RATIO: f32 = 1.5;
+
+/// Shortest rode paid out, in the shallows (m).
+const MIN_SCOPE: f32 = 10.0;
+
+/// Longest rode aboard (m). Deeper than this the anchor can't reach bottom.
+const MAX_RODE: f32 = 150.0;
+
+/// Rode stretch stiffness once taut (N/m). With the ship's mass, a ~12 s surge period.
+const RODE_STIFFNESS: f32 = 35000.0;
+
+/// Rode damping once taut (N·s/m), about a third of critical.
+const RODE_DAMPING: f32 = 40000.0;
+
+/// Pull (N) the anchor holds before it starts dragging. Holds through a stiff breeze,
+/// not a storm.
+const HOLDING_POWER: f32 = 45000.0;
+
+/// Pull (N) above holding power per m/s the anchor drags along the bottom.
+const DRAG_RESISTANCE: f32 = 20000.0;
+
+/// Fastest the anchor ploughs across the bottom (m/s).
+const MAX_DRAG_SPEED: f32 = 2.0;
+
+/// Ship within this distance (m) of a mooring snaps onto it.
+const MOORING_RANGE: f32 = 15.0;
+
+/// Mooring berths off Safe Island (XZ offset from the island centre, m): one either side,
+/// far enough out that the hull clears the beach.
+const MOORINGS: [Vec2; 2] = [Vec2::new(24.0, 0.0), Vec2::new(-24.0, 0.0)];
+
+/// Distance (m) from a berth to the bollard on the shore side.
+const BOLLARD_OFFSET: f32 = 8.0;
+
+#[derive(Component)]
+pub struct Anchor {
+    /// Rode paid out (m).
+    pub scope: f32,
+    /// Rode tension at the last step (N).
+    pub tension: f32,
+    /// Rode taut at the last step: the spring is engaged.
+    pub taut: bool,
+    /// Pulled beyond holding power at the last step.
+    pub dragging: bool,
+}
+
+/// A berth off Safe Island. Its transform is where the ship's centre sits, and her heading.
+#[derive(Component)]
+pub struct MooringPoint;
+
+/// On the mooring point the ship is made fast to. The point carries the joint.
+#[derive(Component)]
+pub struct Moored;
+
+#[derive(Component)]
+struct AnchorRode;
+
+#[derive(Component)]
+struct AnchorUiText;
+
+#[derive(Resource)]
+struct AnchorAssets {
+    anchor_mesh: Handle<Mesh>,
+    anchor_mat: Handle<StandardMaterial>,
+}
+
+#[derive(Resource)]
+struct AnchorUiRoot(Entity);
+
+pub struct AnchorPlugin;
+
+impl Plugin for AnchorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, (spawn_anchor_assets, spawn_moorings, spawn_anchor_ui))
+            .add_systems(
+                FixedUpdate,
+                tend_rode
+                    .before(PhysicsSet::SyncBackend)
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                Update,
+                (anchor_controls, update_rode_visual, update_anchor_ui)
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+/// Height of the seabed (or reef, or beach) under `from`, if the rode can reach it.
+fn seabed_below(context: &RapierContext, from: Vec3) -> Option<f32> {
+    context
+        .cast_ray(from, Vec3::NEG_Y, MAX_RODE, true, QueryFilter::only_fixed())
+        .map(|(_, toi)| from.y - toi)
+}
+
+/// X at the helm or on deck: cast off, weigh anchor, pick up a mooring, or let go.
+fn anchor_controls(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<InputBindings>,
+    mode: Res<PlayerMode>,
+    assets: Res<AnchorAssets>,
+    rapier_context: ReadRapierContext,
+    character_query: Query<&Transform, With<MarineCharacter>>,
+    mut ship_query: Query<(Entity, &mut Transform, &mut Velocity), (With<Ship>, Without<MarineCharacter>)>,
+    anchor_query: Query<Entity, With<Anchor>>,
+    mooring_query: Query<
+        (Entity, &Transform, Has<Moored>),
+        (With<MooringPoint>, Without<Ship>, Without<MarineCharacter>),
+    >,
+) {
+    if !keyboard.just_pressed(bindings.anchor) {
+        return;
+    }
+    let Ok((ship_id, mut ship_tf, mut velocity)) = ship_query.single_mut() else { return };
+    if !mode.can_work_ship(character_query.iter().next(), &ship_tf) {
+        return;
+    }
+
+    if let Some((point_id, _, _)) = mooring_query.iter().find(|(_, _, moored)| *moored) {
+        commands.entity(point_id).remove::<(ImpulseJoint, Moored)>();
+        bevy::log::info!("Cast off from the mooring");
+        return;
+    }
+    if let Some(anchor_id) = anchor_query.iter().next() {
+        commands.entity(anchor_id).despawn();
+        bevy::log::info!("Anchor weighed");
+        return;
+    }
+
+    let berth = mooring_query
+        .iter()
+        .map(|(id, tf, _)| (id, tf, tf.translation.xz().distance(ship_tf.translation.xz())))
+        .filter(|(_, _, d)| *d <= MOORING_RANGE)
+        .min_by(|a, b| a.2.total_cmp(&b.2));
+    if let Some((point_id, point_tf, _)) = berth {
+        ship_tf.translation.x = point_tf.translation.x;
+        ship_tf.translation.z = point_tf.translation.z;
+        ship_tf.rotation = point_tf.rotation;
+        velocity.linvel = Vec3::ZERO;
+        velocity.angvel = Vec3::ZERO;
+        // Pinned in place and heading; free to heave, pitch and roll with the swell.
+        let mooring = GenericJointBuilder::new(
+            JointAxesMask::LIN_X | JointAxesMask::LIN_Z | JointAxesMask::ANG_Y,
+        )
+        .build();
+        commands
+            .entity(point_id)
+            .insert((ImpulseJoint::new(ship_id, TypedJoint::GenericJoint(mooring)), Moored));
+        bevy::log::info!("Moored at Safe Island");
+        return;
+    }
+
+    let Ok(context) = rapier_context.single() else { return };
+    let fairlead = ship_tf.translation + ship_tf.rotation * FAIRLEAD;
+    let Some(bed) = seabed_below(&context, fairlead) else {
+        bevy::log::warn!("Too deep to anchor: no bottom within {:.0} m", MAX_RODE);
+        return;
+    };
+    let scope = ((fairlead.y - bed) * SCOPE_RATIO).clamp(MIN_SCOPE, MAX_RODE);
+    let rode = SpringJointBuilder::new(scope, 0.0, 0.0)
+        .local_anchor1(FAIRLEAD)
+        .local_anchor2(Vec3::ZERO);
+    commands.spawn((
+        Mesh3d(assets.anchor_mesh.clone()),
+        MeshMaterial3d(assets.anchor_mat.clone()),
+        Transform::from_xyz(fairlead.x, bed, fairlead.z),
+        RigidBody::KinematicPositionBased,
+        ImpulseJoint::new(ship_id, rode),
+        Anchor {
+            scope,
+            tension: 0.0,
+            taut: false,
+            dragging: false,
+        },
+    ));
+    bevy::log::info!("Anchor down in {:.0} m, {:.0} m of rode", fairlead.y - bed, scope);
+}
+
+/// Engages the rode spring when it comes taut, and drags the anchor when the pull is too much.
+fn tend_rode(
+    rapier_context: ReadRapierContext,
+    time: Res<Time>,
+    ship_query: Query<&Transform, With<Ship>>,
+    mut anchor_query: Query<(&mut Transform, &mut Anchor, &mut ImpulseJoint), Without<Ship>>,
+) {
+    let Ok(ship_tf) = ship_query.single() else { return };
+    let Ok(context) = rapier_context.single() else { return };
+    let dt = time.delta_secs();
+    for (mut anchor_tf, mut anchor, mut joint) in anchor_query.iter_mut() {
+        let fairlead = ship_tf.translation + ship_tf.rotation * FAIRLEAD;
+        let span = fairlead - anchor_tf.translation;
+        let stretch = span.length() - anchor.scope;
+        anchor.tension = (RODE_STIFFNESS * stretch).max(0.0);
+
+        // Slack rode: no spring and no damping, the ship swings free inside the scope.
+        let taut = stretch > 0.0;
+        if taut != anchor.taut {
+            anchor.taut = taut;
+            let (stiffness, damping) = if taut { (RODE_STIFFNESS, RODE_DAMPING) } else { (0.0, 0.0) };
+            if let TypedJoint::SpringJoint(spring) = &mut joint.data {
+                spring
+                    .data
+                    .set_motor_position(JointAxis::LinX, anchor.scope, stiffness, damping);
+            }
+        }
+
+        anchor.dragging = anchor.tension > HOLDING_POWER;
+        if anchor.dragging {
+            let pull = Vec3::new(span.x, 0.0, span.z).normalize_or_zero();
+            let speed = ((anchor.tension - HOLDING_POWER) / DRAG_RESISTANCE).min(MAX_DRAG_SPEED);
+            let mut next = anchor_tf.translation + pull * speed * dt;
+            if let Some(bed) = seabed_below(&context, Vec3::new(next.x, fairlead.y, next.z)) {
+                next.y = bed;
+            }
+            anchor_tf.translation = next;
+        }
+    }
+}
+
+fn spawn_anchor_assets(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let iron = materials.add(StandardMaterial {
+        base_color: Color::srgb(0.22, 0.22, 0.24),
+        metallic: 0.8,
+        perceptual_roughness: 0.6,
+        ..default()
+    });
+    commands.insert_resource(AnchorAssets {
+        anchor_mesh: meshes.add(Cuboid::new(1.2, 0.5, 0.4)),
+        anchor_mat: iron.clone(),
+    });
+    commands.spawn((
+        Mesh3d(meshes.add(Cylinder::new(0.05, 1.0).mesh().resolution(6))),
+        MeshMaterial3d(iron),
+        Transform::default(),
+        Visibility::Hidden,
+        AnchorRode,
+    ));
+}
+
+fn spawn_moorings(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let bollard = meshes.add(Cylinder::new(0.3, 1.6));
+    let mat = materials.add(StandardMaterial {
+        base_color: Color::srgb(0.35, 0.25, 0.15),
+        perceptual_roughness: 0.9,
+        ..default()
+    });
+    for offset in MOORINGS {
+        let shoreward = -offset.normalize_or_zero() * BOLLARD_OFFSET;
+        commands.spawn((
+            Transform::from_xyz(SPAWN_ISLAND_X + offset.x, 0.0, SPAWN_ISLAND_Z + offset.y),
+            Visibility::default(),
+            RigidBody::Fixed,
+            MooringPoint,
+            children![(
+                Mesh3d(bollard.clone()),
+                MeshMaterial3d(mat.clone()),
+                Transform::from_xyz(shoreward.x, 0.8, shoreward.y),
+            )],
+        ));
+    }
+}
+
+fn update_rode_visual(
+    ship_query: Query<&Transform, With<Ship>>,
+    anchor_query: Query<&Transform, (With<Anchor>, Without<Ship>)>,
+    mut rode_query: Query<
+        (&mut Transform, &mut Visibility),
+        (With<AnchorRode>, Without<Anchor>, Without<Ship>),
+    >,
+) {
+    let Ok((mut rode_tf, mut visibility)) = rode_query.single_mut() else { return };
+    let (Ok(ship_tf), Some(anchor_tf)) = (ship_query.single(), anchor_query.iter().next()) else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+    *visibility = Visibility::Visible;
+    let from = ship_tf.translation + ship_tf.rotation * FAIRLEAD;
+    let to = anchor_tf.translation;
+    let delta = to - from;
+    rode_tf.translation = (from + to) * 0.5;
+    rode_tf.scale = Vec3::new(1.0, delta.length().max(0.1), 1.0);
+    rode_tf.rotation = Quat::from_rotation_arc(Vec3::Y, delta.normalize_or(Vec3::NEG_Y));
+}
+
+fn spawn_anchor_ui(mut commands: Commands) {
+    let (root_id, _) = spawn_hud_row(&mut commands, ANCHOR_SLOT, AnchorUiText);
+    commands.insert_resource(AnchorUiRoot(root_id));
+}
+
+fn update_anchor_ui(
+    mode: Res<PlayerMode>,
+    ui: Res<AnchorUiRoot>,
+    ship_query: Query<&Transform, With<Ship>>,
+    anchor_query: Query<&Anchor>,
+    mooring_query: Query<(&Transform, Has<Moored>), (With<MooringPoint>, Without<Ship>)>,
+    mut visibility_query: Query<&mut Visibility>,
+    mut text_query: Query<&mut Text, With<AnchorUiText>>,
+) {
+    let Ok(mut root_vis) = visibility_query.get_mut(ui.0) else { return };
+    let Ok(ship_tf) = ship_query.single() else {
+        *root_vis = Visibility::Hidden;
+        return;
+    };
+    if !mode.in_boat {
+        *root_vis = Visibility::Hidden;
+        return;
+    }
+    *root_vis = Visibility::Visible;
+    let Ok(mut text) = text_query.single_mut() else { return };
+    let near_berth = mooring_query
+        .iter()
+        .any(|(tf, _)| tf.translation.xz().distance(ship_tf.translation.xz()) <= MOORING_RANGE);
+    let status = if mooring_query.iter().any(|(_, moored)| moored) {
+        "Moored at Safe Island  [X] cast off".to_string()
+    } else if let Some(anchor) = anchor_query.iter().next() {
+        format!(
+            "Anchored, {:.0} m of rode, {:.0} kN{}  [X] weigh",
+            anchor.scope,
+            anchor.tension * 0.001,
+            if anchor.dragging { "  DRAGGING" } else { "" },
+        )
+    } else if near_berth {
+        "Anchor up  [X] moor".to_string()
+    } else {
+        "Anchor up  [X] let go".to_string()
+    };
+    *text = Text::new(status);
+}
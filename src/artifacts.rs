@@ -13,11 +13,74 @@ pub struct Artifact {
     pub item_id: String,
 }
 
+/// Planks for patching the ship's hull. A supply, not loot.
+pub const REPAIR_WOOD: &str = "Repair Wood";
+
+/// Items the crew stocks up on. Everything else in the inventory is loot.
+const SUPPLY_ITEMS: &[&str] = &[REPAIR_WOOD];
+
+/// Mass (kg) of a heavy artifact. Too heavy to swim with; the winch brings them up.
+const HEAVY_ARTIFACT_MASS: f32 = 900.0;
+
+/// Mass (kg) of a hand-carried artifact.
+const ARTIFACT_MASS: f32 = 40.0;
+
+/// Mass (kg) of one plank of repair wood.
+pub const REPAIR_WOOD_MASS: f32 = 20.0;
+
+/// Heavy artifacts go up on the winch, not in hand.
+pub fn is_heavy(item_id: &str) -> bool {
+    item_id.starts_with("Heavy Artifact")
+}
+
+/// Mass (kg) of one item as cargo.
+pub fn item_mass(item_id: &str) -> f32 {
+    if item_id == REPAIR_WOOD {
+        REPAIR_WOOD_MASS
+    } else if is_heavy(item_id) {
+        HEAVY_ARTIFACT_MASS
+    } else {
+        ARTIFACT_MASS
+    }
+}
+
 #[derive(Resource, Default)]
 pub struct Inventory {
     pub items: Vec<String>,
 }
 
+impl Inventory {
+    pub fn count(&self, item_id: &str) -> usize {
+        self.items.iter().filter(|i| *i == item_id).count()
+    }
+
+    /// Removes one `item_id`. False if there was none.
+    pub fn take(&mut self, item_id: &str) -> bool {
+        match self.items.iter().position(|i| i == item_id) {
+            Some(idx) => {
+                self.items.remove(idx);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Total mass (kg) of everything carried.
+    pub fn mass(&self) -> f32 {
+        self.items.iter().map(|i| item_mass(i)).sum()
+    }
+
+    /// Mass (kg) of winched-up heavy artifacts, which ride on the afterdeck.
+    pub fn heavy_mass(&self) -> f32 {
+        self.items.iter().filter(|i| is_heavy(i)).map(|i| item_mass(i)).sum()
+    }
+
+    /// Whether anything beyond supplies is being carried.
+    pub fn has_loot(&self) -> bool {
+        self.items.iter().any(|i| !SUPPLY_ITEMS.contains(&i.as_str()))
+    }
+}
+
 /// Heavy artifact currently attached to winch (child of sub). Cleared on detach or deliver.
 #[derive(Resource, Default)]
 pub struct AttachedArtifact(pub Option<Entity>);
@@ -0,0 +1,345 @@
+//! Ship autopilot – hold a compass heading, run a route of marked waypoints, or keep station
+//! over the sub while the crew dives.
+//!
+//! P cycles Off → Heading → Route → Station. [ and ] nudge the held heading; M marks the
+//! ship's position as the next waypoint on the route. PID loops drive `current_steering`
+//! and, under engine, `current_throttle`; islands inside the look-ahead push the course
+//! aside. Any W/A/S/D at the helm hands control back.
+
+use std::f32::consts::{PI, TAU};
+
+use bevy::prelude::*;
+
+use crate::diving_bell::Submersible;
+use crate::game_state::GameState;
+use crate::hud::{spawn_hud_row, AUTOPILOT_SLOT};
+use crate::islands::IslandCollider;
+use crate::player::PlayerMode;
+use crate::settings::InputBindings;
+use crate::ship::{ship_movement, Ship, ShipPropulsion};
+
+/// Heading nudge per [ / ] press (rad).
+const HEADING_STEP: f32 = 0.1745; // 10°
+
+/// Waypoint counts as reached inside this distance (m).
+const ARRIVAL_RADIUS: f32 = 20.0;
+
+/// Distance (m) from a waypoint at which the engine starts easing off.
+const SLOW_RADIUS: f32 = 80.0;
+
+/// Station keeping steers for the mark beyond this distance (m), and holds heading inside it.
+const STATION_STEER_RADIUS: f32 = 12.0;
+
+/// How far ahead (m) islands are looked for.
+const LOOKAHEAD: f32 = 120.0;
+
+/// Sea room (m) kept beyond an island's radius.
+const CLEARANCE: f32 = 25.0;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum AutopilotMode {
+    #[default]
+    Off,
+    /// Hold `heading`.
+    Heading,
+    /// Steer for each waypoint in turn; off on arrival at the last.
+    Route,
+    /// Hold position over the submersible under engine.
+    Station,
+}
+
+/// Textbook PID with a clamped integral.
+#[derive(Clone, Copy)]
+struct Pid {
+    kp: f32,
+    ki: f32,
+    kd: f32,
+    integral: f32,
+    previous: Option<f32>,
+}
+
+impl Pid {
+    const fn new(kp: f32, ki: f32, kd: f32) -> Self {
+        Self { kp, ki, kd, integral: 0.0, previous: None }
+    }
+
+    fn update(&mut self, error: f32, dt: f32) -> f32 {
+        self.integral = (self.integral + error * dt).clamp(-1.0, 1.0);
+        let derivative = self.previous.map_or(0.0, |p| (error - p) / dt);
+        self.previous = Some(error);
+        self.kp * error + self.ki * self.integral + self.kd * derivative
+    }
+
+    fn reset(&mut self) {
+        self.integral = 0.0;
+        self.previous = None;
+    }
+}
+
+#[derive(Component)]
+pub struct Autopilot {
+    pub mode: AutopilotMode,
+    /// Compass heading held in Heading mode (rad, 0 = -Z, counter-clockwise from above).
+    pub heading: f32,
+    /// Route, next waypoint first (XZ).
+    pub waypoints: Vec<Vec2>,
+    steering: Pid,
+    throttle: Pid,
+}
+
+impl Default for Autopilot {
+    fn default() -> Self {
+        Self {
+            mode: AutopilotMode::Off,
+            heading: 0.0,
+            waypoints: Vec::new(),
+            steering: Pid::new(1.6, 0.1, 2.5),
+            throttle: Pid::new(0.08, 0.01, 0.4),
+        }
+    }
+}
+
+impl Autopilot {
+    pub fn engaged(&self) -> bool {
+        self.mode != AutopilotMode::Off
+    }
+
+    pub fn disengage(&mut self) {
+        self.mode = AutopilotMode::Off;
+        self.steering.reset();
+        self.throttle.reset();
+    }
+}
+
+/// Compass heading of a horizontal direction: 0 along -Z, increasing counter-clockwise.
+fn heading_of(dir: Vec3) -> f32 {
+    (-dir.x).atan2(-dir.z)
+}
+
+/// Wraps an angle into -π..π.
+fn wrap_angle(angle: f32) -> f32 {
+    (angle + PI).rem_euclid(TAU) - PI
+}
+
+#[derive(Component)]
+struct AutopilotUiText;
+
+#[derive(Resource)]
+struct AutopilotUiRoot(Entity);
+
+pub struct AutopilotPlugin;
+
+impl Plugin for AutopilotPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_autopilot_ui)
+            .add_systems(
+                Update,
+                (
+                    autopilot_controls
+                        .run_if(in_state(GameState::Playing))
+                        .run_if(|mode: Res<PlayerMode>| mode.in_boat),
+                    update_autopilot_ui.run_if(in_state(GameState::Playing)),
+                ),
+            )
+            .add_systems(
+                FixedUpdate,
+                steer_autopilot
+                    .before(ship_movement)
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+fn autopilot_controls(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<InputBindings>,
+    mut query: Query<(&Transform, &mut Ship, &mut Autopilot)>,
+) {
+    for (transform, mut ship, mut autopilot) in query.iter_mut() {
+        let manual = [bindings.forward, bindings.back, bindings.left, bindings.right]
+            .iter()
+            .any(|key| keyboard.just_pressed(*key));
+        if manual && autopilot.engaged() {
+            autopilot.disengage();
+            bevy::log::info!("Autopilot off: manual helm");
+        }
+        if keyboard.just_pressed(bindings.mark_waypoint) {
+            let here = transform.translation.xz();
+            autopilot.waypoints.push(here);
+            bevy::log::info!("Waypoint {} marked", autopilot.waypoints.len());
+        }
+        if keyboard.just_pressed(bindings.heading_left) {
+            autopilot.heading = wrap_angle(autopilot.heading + HEADING_STEP);
+        }
+        if keyboard.just_pressed(bindings.heading_right) {
+            autopilot.heading = wrap_angle(autopilot.heading - HEADING_STEP);
+        }
+        if keyboard.just_pressed(bindings.autopilot) {
+            let next = match autopilot.mode {
+                AutopilotMode::Off => AutopilotMode::Heading,
+                AutopilotMode::Heading if autopilot.waypoints.is_empty() => AutopilotMode::Station,
+                AutopilotMode::Heading => AutopilotMode::Route,
+                AutopilotMode::Route => AutopilotMode::Station,
+                AutopilotMode::Station => AutopilotMode::Off,
+            };
+            autopilot.disengage();
+            autopilot.mode = next;
+            match next {
+                AutopilotMode::Heading => autopilot.heading = heading_of(transform.forward().as_vec3()),
+                // Holding station against wind and current needs the engine.
+                AutopilotMode::Station => ship.propulsion = ShipPropulsion::Engine,
+                _ => {}
+            }
+            if next == AutopilotMode::Off {
+                ship.current_throttle = 0.0;
+                ship.current_steering = 0.0;
+            }
+            bevy::log::info!("Autopilot: {:?}", next);
+        }
+    }
+}
+
+/// Pushes the desired course around islands inside the look-ahead: away from the island,
+/// and round it on the side the course already favours.
+fn avoid_islands(position: Vec3, desired: Vec3, islands: &[(Vec3, f32)]) -> Vec3 {
+    let mut course = desired;
+    for (centre, radius) in islands {
+        let offset = Vec3::new(position.x - centre.x, 0.0, position.z - centre.z);
+        let distance = offset.length();
+        let keep_out = radius + CLEARANCE;
+        if distance > keep_out + LOOKAHEAD || distance < 0.01 {
+            continue;
+        }
+        let away = offset / distance;
+        // Only islands we're heading toward.
+        if desired.dot(-away) <= 0.0 {
+            continue;
+        }
+        let urgency = (1.0 - (distance - keep_out) / LOOKAHEAD).clamp(0.0, 1.0);
+        let mut round = Vec3::Y.cross(away);
+        if round.dot(desired) < 0.0 {
+            round = -round;
+        }
+        course += (away + round) * urgency * 1.5;
+    }
+    course.normalize_or(desired)
+}
+
+fn steer_autopilot(
+    time: Res<Time>,
+    sub_query: Query<&Transform, With<Submersible>>,
+    island_query: Query<(&Transform, &IslandCollider)>,
+    mut query: Query<(&Transform, &mut Ship, &mut Autopilot), Without<Submersible>>,
+) {
+    let dt = time.delta_secs();
+    let islands: Vec<(Vec3, f32)> = island_query
+        .iter()
+        .map(|(tf, island)| (tf.translation, island.radius))
+        .collect();
+    for (transform, mut ship, mut autopilot) in query.iter_mut() {
+        let position = transform.translation;
+        let forward = transform.forward().as_vec3();
+        let heading = heading_of(forward);
+
+        // Desired course and, under engine, the distance still to run.
+        let (course, run) = match autopilot.mode {
+            AutopilotMode::Off => continue,
+            AutopilotMode::Heading => {
+                let course = Vec3::new(-autopilot.heading.sin(), 0.0, -autopilot.heading.cos());
+                (course, None)
+            }
+            AutopilotMode::Route => {
+                while autopilot
+                    .waypoints
+                    .first()
+                    .is_some_and(|w| w.distance(position.xz()) <= ARRIVAL_RADIUS)
+                {
+                    autopilot.waypoints.remove(0);
+                    bevy::log::info!("Waypoint reached, {} to go", autopilot.waypoints.len());
+                }
+                let Some(next) = autopilot.waypoints.first().copied() else {
+                    autopilot.disengage();
+                    ship.current_throttle = 0.0;
+                    ship.current_steering = 0.0;
+                    bevy::log::info!("Autopilot off: route complete");
+                    continue;
+                };
+                let to = Vec3::new(next.x - position.x, 0.0, next.y - position.z);
+                (to.normalize_or(forward), Some(to.length()))
+            }
+            AutopilotMode::Station => {
+                let Ok(sub_tf) = sub_query.single() else { continue };
+                let to = Vec3::new(sub_tf.translation.x - position.x, 0.0, sub_tf.translation.z - position.z);
+                let course = if to.length() > STATION_STEER_RADIUS { to.normalize() } else { forward };
+                // Signed distance along the keel: station keeping can go astern.
+                let along = to.dot(Vec3::new(forward.x, 0.0, forward.z).normalize_or_zero());
+                (course, Some(along))
+            }
+        };
+
+        let course = avoid_islands(position, course, &islands);
+        let error = wrap_angle(heading_of(course) - heading);
+        ship.current_steering = autopilot.steering.update(error, dt).clamp(-1.0, 1.0);
+
+        if ship.propulsion == ShipPropulsion::Engine {
+            let throttle = match (autopilot.mode, run) {
+                (AutopilotMode::Station, Some(along)) => autopilot.throttle.update(along, dt),
+                (AutopilotMode::Route, Some(remaining)) => {
+                    // Ease off on approach and while turning hard.
+                    let approach = (remaining / SLOW_RADIUS).clamp(0.3, 1.0);
+                    approach * error.cos().max(0.2)
+                }
+                _ => 1.0,
+            };
+            // Don't race away from a sub we're holding over.
+            let limit = if autopilot.mode == AutopilotMode::Station { 0.6 } else { 1.0 };
+            ship.current_throttle = throttle.clamp(-limit, limit);
+        }
+    }
+}
+
+fn spawn_autopilot_ui(mut commands: Commands) {
+    let (root_id, _) = spawn_hud_row(&mut commands, AUTOPILOT_SLOT, AutopilotUiText);
+    commands.insert_resource(AutopilotUiRoot(root_id));
+}
+
+fn update_autopilot_ui(
+    mode: Res<PlayerMode>,
+    ui: Res<AutopilotUiRoot>,
+    ship_query: Query<(&Transform, &Autopilot)>,
+    mut visibility_query: Query<&mut Visibility>,
+    mut text_query: Query<&mut Text, With<AutopilotUiText>>,
+) {
+    let Ok(mut root_vis) = visibility_query.get_mut(ui.0) else { return };
+    let Ok((transform, autopilot)) = ship_query.single() else {
+        *root_vis = Visibility::Hidden;
+        return;
+    };
+    if !mode.in_boat {
+        *root_vis = Visibility::Hidden;
+        return;
+    }
+    *root_vis = Visibility::Visible;
+    let Ok(mut text) = text_query.single_mut() else { return };
+    // Compass bearing, clockwise from north (-Z).
+    let bearing = |h: f32| (-h).to_degrees().rem_euclid(360.0);
+    let status = match autopilot.mode {
+        AutopilotMode::Off => "off".to_string(),
+        AutopilotMode::Heading => format!("holding {:03.0}°  [ ] adjust", bearing(autopilot.heading)),
+        AutopilotMode::Route => match autopilot.waypoints.first() {
+            Some(next) => format!(
+                "route, {} waypoints, next {:.0} m",
+                autopilot.waypoints.len(),
+                next.distance(transform.translation.xz())
+            ),
+            None => "route".to_string(),
+        },
+        AutopilotMode::Station => "keeping station over the sub".to_string(),
+    };
+    *text = Text::new(format!(
+        "Heading {:03.0}°  Autopilot {}  [P] mode [M] mark ({})",
+        bearing(heading_of(transform.forward().as_vec3())),
+        status,
+        autopilot.waypoints.len(),
+    ));
+}
@@ -0,0 +1,214 @@
+//! Submersible ballast and trim – true buoyancy for the dynamic sub.
+//!
+//! Flood the tanks (V) to go heavy and sink, blow them (B) to go light and rise; the pumps
+//! run off the battery. Trim (, and .) pumps water fore and aft to pitch the bow, once the
+//! attitude stabiliser is off (it holds her level otherwise). The emergency blow (Backspace)
+//! empties the tanks at once and drops the shot weight for a fast ascent; the weight is
+//! rehung and the air flask recharged once surfaced.
+
+use bevy::prelude::*;
+
+use crate::diving_bell::{is_surfaced, submersible_movement, Submersible};
+use crate::game_state::GameState;
+use crate::hud::{spawn_hud_row, BALLAST_SLOT};
+use crate::ocean::OceanSolver;
+use crate::player::PlayerMode;
+use crate::settings::InputBindings;
+use crate::ship::{GRAVITY, WATER_DENSITY};
+use crate::sim_clock::SIM_DT;
+use crate::sub_power::{battery_budget, SubBattery};
+
+/// Ballast tank capacity (m³).
+const TANK_VOLUME: f32 = 2.0;
+
+/// Tank fraction flooded or blown per second.
+const BALLAST_RATE: f32 = 0.08;
+
+/// Trim travel per second, full bow-up to full bow-down in four seconds.
+const TRIM_RATE: f32 = 0.5;
+
+/// Pitching moment (N·m) with the trim water all at one end.
+const TRIM_MOMENT: f32 = 4.0e4;
+
+/// Battery charge per second while a pump runs.
+const PUMP_DRAW: f32 = 0.15;
+
+/// Shot weight (kg) carried for the emergency blow.
+const DROP_WEIGHT: f32 = 1000.0;
+
+#[derive(Component)]
+pub struct Ballast {
+    /// Tanks flooded, 0 = blown dry, 1 = full.
+    pub fill: f32,
+    /// -1 = bow up, 1 = bow down.
+    pub trim: f32,
+    /// Shot weight still hung; dropped by the emergency blow.
+    pub weight_hung: bool,
+    /// Tank pump from the helm: 1 = flooding, -1 = blowing, 0 = off.
+    pub fill_pump: f32,
+    /// Trim pump from the helm: 1 = toward bow down, -1 = toward bow up, 0 = off.
+    pub trim_pump: f32,
+}
+
+impl Default for Ballast {
+    fn default() -> Self {
+        Self {
+            fill: 0.0,
+            trim: 0.0,
+            weight_hung: true,
+            fill_pump: 0.0,
+            trim_pump: 0.0,
+        }
+    }
+}
+
+impl Ballast {
+    /// Weight (N, downward) of the water in the tanks, less the lift from dropped shot.
+    pub fn weight(&self) -> f32 {
+        let shot = if self.weight_hung { 0.0 } else { DROP_WEIGHT };
+        (self.fill * TANK_VOLUME * WATER_DENSITY - shot) * GRAVITY
+    }
+
+    /// Bow-down pitching moment (N·m) from the trim tanks.
+    pub fn trim_moment(&self) -> f32 {
+        self.trim * TRIM_MOMENT
+    }
+}
+
+#[derive(Component)]
+struct BallastUiText;
+
+#[derive(Resource)]
+struct BallastUiRoot(Entity);
+
+pub struct BallastPlugin;
+
+impl Plugin for BallastPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_ballast_ui)
+            .add_systems(
+                Update,
+                (
+                    ballast_input.run_if(in_state(GameState::Playing)),
+                    rehang_weight.run_if(in_state(GameState::Playing)),
+                    update_ballast_ui.run_if(in_state(GameState::Playing)),
+                ),
+            )
+            .add_systems(
+                FixedUpdate,
+                run_pumps
+                    .after(battery_budget)
+                    .before(submersible_movement)
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+/// Latches the pump switches from the helm; they stop when the pilot leaves. The emergency
+/// blow needs no pump and acts at once.
+fn ballast_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<InputBindings>,
+    mode: Res<PlayerMode>,
+    mut query: Query<&mut Ballast>,
+) {
+    for mut ballast in query.iter_mut() {
+        if !mode.in_submersible {
+            ballast.fill_pump = 0.0;
+            ballast.trim_pump = 0.0;
+            continue;
+        }
+        if keyboard.just_pressed(bindings.emergency_blow) && ballast.weight_hung {
+            ballast.fill = 0.0;
+            ballast.trim = 0.0;
+            ballast.weight_hung = false;
+            ballast.fill_pump = 0.0;
+            ballast.trim_pump = 0.0;
+            bevy::log::warn!("Emergency blow: tanks dry, shot weight dropped");
+            continue;
+        }
+        ballast.fill_pump = match (
+            keyboard.pressed(bindings.ballast_flood),
+            keyboard.pressed(bindings.ballast_blow),
+        ) {
+            (true, false) => 1.0,
+            (false, true) => -1.0,
+            _ => 0.0,
+        };
+        ballast.trim_pump = match (
+            keyboard.pressed(bindings.trim_bow_down),
+            keyboard.pressed(bindings.trim_bow_up),
+        ) {
+            (true, false) => 1.0,
+            (false, true) => -1.0,
+            _ => 0.0,
+        };
+    }
+}
+
+/// Pumps for the tanks and trim, while the battery lasts.
+pub(crate) fn run_pumps(mut query: Query<(&mut Ballast, &mut SubBattery)>) {
+    for (mut ballast, mut battery) in query.iter_mut() {
+        let pumps = (ballast.fill_pump != 0.0) as u8 + (ballast.trim_pump != 0.0) as u8;
+        if pumps == 0 || !battery.draw(PUMP_DRAW * pumps as f32 * SIM_DT) {
+            continue;
+        }
+        ballast.fill = (ballast.fill + ballast.fill_pump * BALLAST_RATE * SIM_DT).clamp(0.0, 1.0);
+        ballast.trim = (ballast.trim + ballast.trim_pump * TRIM_RATE * SIM_DT).clamp(-1.0, 1.0);
+    }
+}
+
+/// Surfaced, the crew rehangs the shot and recharges the blow flask.
+fn rehang_weight(ocean: Res<OceanSolver>, mut query: Query<(&Transform, &mut Ballast)>) {
+    for (transform, mut ballast) in query.iter_mut() {
+        if !ballast.weight_hung && is_surfaced(&ocean, transform.translation) {
+            ballast.weight_hung = true;
+            bevy::log::info!("Shot weight rehung; emergency blow ready");
+        }
+    }
+}
+
+fn spawn_ballast_ui(mut commands: Commands) {
+    let (root_id, _) = spawn_hud_row(&mut commands, BALLAST_SLOT, BallastUiText);
+    commands.insert_resource(BallastUiRoot(root_id));
+}
+
+fn update_ballast_ui(
+    mode: Res<PlayerMode>,
+    ui: Res<BallastUiRoot>,
+    ballast_query: Query<(&Ballast, &Submersible, &Transform)>,
+    mut visibility_query: Query<&mut Visibility>,
+    mut text_query: Query<&mut Text, With<BallastUiText>>,
+) {
+    let Ok(mut root_vis) = visibility_query.get_mut(ui.0) else { return };
+    let Ok((ballast, sub, transform)) = ballast_query.single() else {
+        *root_vis = Visibility::Hidden;
+        return;
+    };
+    if !mode.in_submersible {
+        *root_vis = Visibility::Hidden;
+        return;
+    }
+    *root_vis = Visibility::Visible;
+    let Ok(mut text) = text_query.single_mut() else { return };
+    let trim = if ballast.trim > 0.05 {
+        "bow down"
+    } else if ballast.trim < -0.05 {
+        "bow up"
+    } else {
+        "level"
+    };
+    let blow = if ballast.weight_hung { "[Backspace] emergency blow" } else { "shot dropped" };
+    let pitch = transform.forward().y.clamp(-1.0, 1.0).asin().to_degrees();
+    let roll = transform.right().y.clamp(-1.0, 1.0).asin().to_degrees();
+    *text = Text::new(format!(
+        "Ballast {:.0}% [V/B]  Trim {} {:.0}% [,/.]  {}\nPitch {:+.0}°  Roll {:+.0}° [J/K]  Stabiliser {} [O]",
+        ballast.fill * 100.0,
+        trim,
+        ballast.trim.abs() * 100.0,
+        blow,
+        pitch,
+        -roll,
+        if sub.stabilise { "on" } else { "off" },
+    ));
+}
@@ -8,10 +8,13 @@ use bevy::input::mouse::AccumulatedMouseMotion;
 use bevy::ui::{AlignItems, FlexDirection, JustifyContent};
 
 use bevy_rapier3d::prelude::*;
+use crate::currents::OceanCurrents;
 use crate::game_state::GameState;
-use crate::ocean::{OceanSolver, SEA_LEVEL};
+use crate::ocean::OceanSolver;
 use crate::player::{PlayerCamera, PlayerMode};
 use crate::settings::{GameSettings, InputBindings};
+use crate::tide::Tide;
+use crate::wake::WakeSource;
 use crate::world::{character_respawn_position, MAP_SCALE_FROM_LEGACY, SPAWN_ISLAND_X, SPAWN_ISLAND_Z};
 
 /// Deck offset from ship center (character stands on ship).
@@ -34,8 +37,19 @@ const PRESSURE_DRAIN_MULTIPLIER: f32 = 3.0;
 pub struct MarineCharacter {
     pub walk_speed: f32,
     pub jump_velocity: f32,
+    /// Jump pressed since the last fixed step. Set per frame, consumed by character_movement.
+    pub jump_queued: bool,
+    /// Velocity carried from the deck underfoot. Kept through a jump, dropped on fixed
+    /// ground, bled off by the water.
+    pub platform_velocity: Vec3,
 }
 
+/// A body the character rides when standing on it: the ship's deck. Its velocity at the
+/// character's feet is injected into the controller's move ("velocity injection", proj.md).
+#[derive(Component, Default)]
+#[require(ReadMassProperties)]
+pub struct MovingPlatform;
+
 #[derive(Component)]
 pub struct CharacterVelocity(pub Vec3);
 
@@ -75,7 +89,7 @@ impl Plugin for CharacterPlugin {
                     character_mouse_look
                         .run_if(in_state(GameState::Playing))
                         .run_if(|mode: Res<PlayerMode>| !mode.in_vehicle()),
-                    character_movement
+                    queue_character_jump
                         .run_if(in_state(GameState::Playing))
                         .run_if(|mode: Res<PlayerMode>| !mode.in_vehicle()),
                     character_oxygen
@@ -83,6 +97,14 @@ impl Plugin for CharacterPlugin {
                         .run_if(|mode: Res<PlayerMode>| !mode.in_vehicle()),
                     update_character_oxygen_ui.run_if(in_state(GameState::Playing)),
                 ),
+            )
+            // Kinematic controller moves are consumed once per physics step, so steer on the same clock.
+            .add_systems(
+                FixedUpdate,
+                character_movement
+                    .before(PhysicsSet::SyncBackend)
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(|mode: Res<PlayerMode>| !mode.in_vehicle()),
             );
     }
 }
@@ -110,7 +132,10 @@ fn spawn_character(
         MarineCharacter {
             walk_speed: 4.0,
             jump_velocity: 6.0,
+            jump_queued: false,
+            platform_velocity: Vec3::ZERO,
         },
+        WakeSource::new(0.6, 0.08),
         CharacterOxygen {
             max: 60.0,
             current: 60.0,
@@ -173,6 +198,7 @@ fn spawn_character_oxygen_ui(mut commands: Commands) {
 
 fn character_oxygen(
     ocean: Res<OceanSolver>,
+    tide: Res<Tide>,
     mut query: Query<(
         &mut Transform,
         &mut CharacterOxygen,
@@ -186,7 +212,7 @@ fn character_oxygen(
         let underwater = pos.y < wave_height + SURFACE_EXIT_MARGIN;
 
         if underwater {
-            let depth = SEA_LEVEL - pos.y;
+            let depth = tide.level - pos.y;
             let drain_mult = if depth > PRESSURE_DEPTH_THRESHOLD {
                 PRESSURE_DRAIN_MULTIPLIER
             } else {
@@ -216,7 +242,7 @@ fn update_character_oxygen_ui(
     mut fill_query: Query<&mut Node, With<CharacterOxygenBarFill>>,
 ) {
     let mut root_vis = visibility_query.get_mut(oxygen_ui.root).unwrap();
-    if mode.in_vehicle() {
+    if mode.in_boat {
         *root_vis = Visibility::Hidden;
         return;
     }
@@ -226,7 +252,9 @@ fn update_character_oxygen_ui(
     };
     let wave_height = ocean.wave_height_at(transform.translation);
     let underwater = transform.translation.y < wave_height + SURFACE_EXIT_MARGIN;
-    if !underwater {
+    // In the sub the pilot's own oxygen only shows once the bell has run dry.
+    let shown = if mode.in_submersible { oxygen.current < oxygen.max } else { underwater };
+    if !shown {
         *root_vis = Visibility::Hidden;
         return;
     }
@@ -257,21 +285,46 @@ fn character_mouse_look(
     }
 }
 
+/// `just_pressed` lasts one frame, which may see zero or several fixed steps: latch it.
+fn queue_character_jump(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<InputBindings>,
+    mut query: Query<&mut MarineCharacter>,
+) {
+    if keyboard.just_pressed(bindings.jump) {
+        for mut char in query.iter_mut() {
+            char.jump_queued = true;
+        }
+    }
+}
+
 fn character_movement(
     keyboard: Res<ButtonInput<KeyCode>>,
     bindings: Res<InputBindings>,
     ocean: Res<OceanSolver>,
+    currents: Res<OceanCurrents>,
     mut query: Query<(
-        &MarineCharacter,
+        &mut MarineCharacter,
         &mut CharacterVelocity,
-        &Transform,
+        &mut CharacterLook,
+        &mut Transform,
         &mut KinematicCharacterController,
+        Option<&KinematicCharacterControllerOutput>,
     )>,
+    platform_query: Query<
+        (&Transform, &Velocity, &ReadMassProperties),
+        (With<MovingPlatform>, Without<MarineCharacter>),
+    >,
     time: Res<Time>,
 ) {
     let dt = time.delta_secs();
-    for (char, mut vel, transform, mut controller) in query.iter_mut() {
+    for (mut char, mut vel, mut look, mut transform, mut controller, output) in query.iter_mut() {
         let pos = transform.translation;
+        let grounded = output.is_some_and(|o| o.grounded);
+        // Deck underfoot after the last move: ride its velocity at our feet, turn with it.
+        let platform = output
+            .filter(|o| o.grounded)
+            .and_then(|o| o.collisions.iter().find_map(|c| platform_query.get(c.entity).ok()));
         let wave_height = ocean.wave_height_at(pos);
         let underwater = pos.y < wave_height + SURFACE_EXIT_MARGIN;
 
@@ -280,8 +333,10 @@ fn character_movement(
             // Near surface: neutral buoyancy so player can tread water and climb onto ship.
             let near_surface = pos.y > wave_height - 0.3;
             let sink_rate = if near_surface { 0.3 } else { 1.8 };
+            let current = currents.sample(pos, ocean.sea_level);
             vel.0.y -= sink_rate * dt;
             vel.0.y *= 1.0 - SWIM_DRAG * dt;
+            vel.0.y += current.y * SWIM_DRAG * dt;
 
             if keyboard.pressed(bindings.ascend) {
                 vel.0.y += SWIM_ASCEND_SPEED * dt;
@@ -305,19 +360,23 @@ fn character_movement(
                 input.x += 1.0;
             }
 
+            // Swim relative to the water: idle drifts with the current.
             if input.length_squared() > 0.0 {
                 let dir = transform.rotation * input.normalize();
-                vel.0.x = dir.x * SWIM_SPEED;
-                vel.0.z = dir.z * SWIM_SPEED;
+                vel.0.x = dir.x * SWIM_SPEED + current.x;
+                vel.0.z = dir.z * SWIM_SPEED + current.z;
             } else {
-                vel.0.x *= 1.0 - SWIM_DRAG * dt;
-                vel.0.z *= 1.0 - SWIM_DRAG * dt;
+                vel.0.x += (current.x - vel.0.x) * SWIM_DRAG * dt;
+                vel.0.z += (current.z - vel.0.z) * SWIM_DRAG * dt;
             }
         } else {
             // Walking: gravity, jump, WASD
+            if grounded {
+                vel.0.y = vel.0.y.max(0.0);
+            }
             vel.0.y -= 9.8 * dt;
 
-            if keyboard.just_pressed(bindings.jump) {
+            if char.jump_queued {
                 vel.0.y = char.jump_velocity;
             }
 
@@ -345,7 +404,19 @@ fn character_movement(
             }
         }
 
-        let mut delta = vel.0 * dt;
+        if let Some((platform_tf, platform_vel, mass)) = platform {
+            let centre_of_mass = platform_tf.translation
+                + platform_tf.rotation * mass.get().local_center_of_mass;
+            char.platform_velocity = platform_vel.linear_velocity_at_point(pos, centre_of_mass);
+            look.yaw += platform_vel.angvel.y * dt;
+            transform.rotation = Quat::from_euler(EulerRot::YXZ, look.yaw, look.pitch, 0.0);
+        } else if underwater {
+            char.platform_velocity *= 1.0 - (SWIM_DRAG * dt).min(1.0);
+        } else if grounded {
+            char.platform_velocity = Vec3::ZERO;
+        }
+
+        let mut delta = (vel.0 + char.platform_velocity) * dt;
 
         // Floor clamp: don't go below seafloor
         const FLOOR_Y: f32 = -80.0;
@@ -355,5 +426,6 @@ fn character_movement(
         }
 
         controller.translation = Some(delta);
+        char.jump_queued = false;
     }
 }
@@ -0,0 +1,280 @@
+//! Docking cradle – the sub's berth on the ship's afterdeck, launch and recovery.
+//!
+//! Docked, the sub is locked into the cradle (kinematic, riding the ship) while her bell and
+//! battery recharge. Launch (E at the winch console) swings her out astern on the winch and
+//! lowers her to the water; recovery (E at the console with her floating under the stern)
+//! hoists her back aboard. Both need the winch cable rigged. The docked state is saved.
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+use crate::diving_bell::{DivingBell, Submersible, SUB_HEIGHT};
+use crate::game_state::GameState;
+use crate::interaction::{Interactable, InteractKind};
+use crate::player::VEHICLE_ENTER_RANGE;
+use crate::ship::{Ship, HULL_HALF_EXTENTS, SHIP_SCALE};
+use crate::sim_clock::SIM_DT;
+use crate::sub_power::SubBattery;
+use crate::winch::WinchState;
+
+/// Cradle on the afterdeck under the winch, ship body space (m): the sub's keel on the deck.
+/// Close enough that the cable hangs slack even reeled right in.
+const CRADLE: Vec3 = Vec3::new(0.0, HULL_HALF_EXTENTS.y * SHIP_SCALE + SUB_HEIGHT * 0.5, 7.5);
+
+/// Winch console at the stern rail, ship body space (m). Off to one side of the cradle, so
+/// launching and boarding the docked sub are separate interactions.
+const CRADLE_CONTROL: Vec3 = Vec3::new(4.5, HULL_HALF_EXTENTS.y * SHIP_SCALE + 1.0, 9.0);
+
+/// Top of the swing astern, where the sub hangs before she's lowered (m).
+const OVER_STERN: Vec3 = Vec3::new(0.0, 4.0, 25.0);
+
+/// Where a launched sub is set down, floating astern clear of the hull (m).
+const LAUNCH_POINT: Vec3 = Vec3::new(0.0, -2.5, 25.0);
+
+/// Sub within this distance (m) of the launch point can be hoisted aboard.
+const RECOVERY_RANGE: f32 = 15.0;
+
+/// Seconds for a launch or a recovery.
+const HANDLING_TIME: f32 = 8.0;
+
+/// Cable paid out before a launch (m), so the winch doesn't fight the swing.
+const LAUNCH_CABLE: f32 = 30.0;
+
+/// Battery charge per second from the ship's generator while docked.
+const DOCK_CHARGE_RATE: f32 = 3.0;
+
+/// Bell oxygen per second from the deck air hose while docked.
+const DOCK_AIR_RATE: f32 = 25.0;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DockState {
+    Docked,
+    Lowering,
+    Afloat,
+    Hoisting,
+}
+
+#[derive(Resource)]
+pub struct Cradle {
+    pub state: DockState,
+    /// 0..1 through a launch or recovery.
+    progress: f32,
+    /// Ship-space position and rotation the sub was picked up from.
+    start: (Vec3, Quat),
+    /// Launch or recovery asked for at the console, taken up next step.
+    requested: bool,
+}
+
+impl Default for Cradle {
+    fn default() -> Self {
+        Self {
+            state: DockState::Docked,
+            progress: 0.0,
+            start: (CRADLE, Quat::IDENTITY),
+            requested: false,
+        }
+    }
+}
+
+impl Cradle {
+    /// Docked (or on the way up): what the save records.
+    pub fn is_docked(&self) -> bool {
+        matches!(self.state, DockState::Docked | DockState::Hoisting)
+    }
+
+    /// Ask for a launch if docked, a recovery if afloat.
+    pub fn request(&mut self) {
+        self.requested = true;
+    }
+}
+
+/// Docked state from a loaded save.
+#[derive(Resource, Default)]
+pub struct RestoreDock(pub Option<bool>);
+
+/// Where the launch and recovery interaction sits, kept at the winch console.
+#[derive(Component)]
+struct CradleControl;
+
+pub struct CradlePlugin;
+
+impl Plugin for CradlePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Cradle>()
+            .init_resource::<RestoreDock>()
+            .add_systems(Startup, spawn_cradle_control)
+            .add_systems(
+                FixedUpdate,
+                (start_handling, carry_sub, dock_services)
+                    .chain()
+                    .before(PhysicsSet::SyncBackend)
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                Update,
+                (restore_dock, sync_cradle_body, update_cradle_control)
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+fn spawn_cradle_control(mut commands: Commands) {
+    commands.spawn((
+        Transform::default(),
+        Interactable {
+            kind: InteractKind::LaunchSub,
+            range: VEHICLE_ENTER_RANGE,
+        },
+        CradleControl,
+    ));
+}
+
+/// Takes up a request from the console: launch if docked, recovery if the sub is floating
+/// under the stern. Either way the winch cable has to be rigged.
+fn start_handling(
+    mut cradle: ResMut<Cradle>,
+    mut winch: ResMut<WinchState>,
+    ship_query: Query<&Transform, With<Ship>>,
+    sub_query: Query<&Transform, With<Submersible>>,
+) {
+    if !std::mem::take(&mut cradle.requested) {
+        return;
+    }
+    let (Ok(ship_tf), Ok(sub_tf)) = (ship_query.single(), sub_query.single()) else { return };
+    if !winch.tethered {
+        bevy::log::warn!("Rig the winch cable to the sub first");
+        return;
+    }
+    let local = ship_tf.rotation.inverse() * (sub_tf.translation - ship_tf.translation);
+    match cradle.state {
+        DockState::Docked => {
+            winch.cable_length = winch.cable_length.max(LAUNCH_CABLE);
+            cradle.state = DockState::Lowering;
+            cradle.progress = 0.0;
+            bevy::log::info!("Launching the sub");
+        }
+        DockState::Afloat if local.distance(LAUNCH_POINT) <= RECOVERY_RANGE => {
+            cradle.start = (local, ship_tf.rotation.inverse() * sub_tf.rotation);
+            cradle.state = DockState::Hoisting;
+            cradle.progress = 0.0;
+            bevy::log::info!("Recovering the sub");
+        }
+        DockState::Afloat => {
+            bevy::log::info!("Bring the sub in under the stern to recover her");
+        }
+        DockState::Lowering | DockState::Hoisting => {}
+    }
+}
+
+/// Holds a docked sub in the cradle and swings her out or in along the winch path, in the
+/// ship's frame so she rides the ship's motion. A launch ends with her let go, afloat.
+fn carry_sub(
+    mut cradle: ResMut<Cradle>,
+    ship_query: Query<(&Transform, &Velocity), (With<Ship>, Without<Submersible>)>,
+    mut sub_query: Query<(&mut Transform, &mut Velocity), With<Submersible>>,
+) {
+    if cradle.state == DockState::Afloat {
+        return;
+    }
+    let Ok((ship_tf, ship_vel)) = ship_query.single() else { return };
+    let Ok((mut sub_tf, mut sub_vel)) = sub_query.single_mut() else { return };
+
+    if cradle.state != DockState::Docked {
+        cradle.progress = (cradle.progress + SIM_DT / HANDLING_TIME).min(1.0);
+    }
+    let t = cradle.progress;
+    let (local, rotation) = match cradle.state {
+        DockState::Docked => (CRADLE, Quat::IDENTITY),
+        DockState::Lowering if t < 0.5 => (CRADLE.lerp(OVER_STERN, t * 2.0), Quat::IDENTITY),
+        DockState::Lowering => (OVER_STERN.lerp(LAUNCH_POINT, t * 2.0 - 1.0), Quat::IDENTITY),
+        DockState::Hoisting if t < 0.5 => {
+            let (from, turned) = cradle.start;
+            (from.lerp(OVER_STERN, t * 2.0), turned.slerp(Quat::IDENTITY, t * 2.0))
+        }
+        DockState::Hoisting => (OVER_STERN.lerp(CRADLE, t * 2.0 - 1.0), Quat::IDENTITY),
+        DockState::Afloat => return,
+    };
+    sub_tf.translation = ship_tf.translation + ship_tf.rotation * local;
+    sub_tf.rotation = ship_tf.rotation * rotation;
+
+    if t < 1.0 {
+        return;
+    }
+    match cradle.state {
+        DockState::Lowering => {
+            *sub_vel = Velocity {
+                linvel: ship_vel.linvel + ship_vel.angvel.cross(ship_tf.rotation * local),
+                angvel: Vec3::ZERO,
+            };
+            cradle.state = DockState::Afloat;
+            bevy::log::info!("Sub afloat");
+        }
+        DockState::Hoisting => {
+            cradle.state = DockState::Docked;
+            bevy::log::info!("Sub docked");
+        }
+        _ => {}
+    }
+}
+
+/// The ship's generator charges a docked sub; her bell fills from the air hose alongside.
+fn dock_services(
+    cradle: Res<Cradle>,
+    mut query: Query<(&mut SubBattery, &mut DivingBell)>,
+) {
+    if cradle.state != DockState::Docked {
+        return;
+    }
+    for (mut battery, mut bell) in query.iter_mut() {
+        battery.charge = (battery.charge + DOCK_CHARGE_RATE * SIM_DT).min(battery.capacity);
+        bell.current_oxygen = (bell.current_oxygen + DOCK_AIR_RATE * SIM_DT).min(bell.max_oxygen);
+    }
+}
+
+/// Kinematic with her colliders off while in the winch's hands; a free body afloat.
+fn sync_cradle_body(
+    mut commands: Commands,
+    cradle: Res<Cradle>,
+    sub_query: Query<Entity, With<Submersible>>,
+) {
+    if !cradle.is_changed() {
+        return;
+    }
+    let Ok(sub_id) = sub_query.single() else { return };
+    if cradle.state == DockState::Afloat {
+        commands
+            .entity(sub_id)
+            .insert(RigidBody::Dynamic)
+            .remove::<ColliderDisabled>();
+    } else {
+        commands
+            .entity(sub_id)
+            .insert((RigidBody::KinematicPositionBased, ColliderDisabled));
+    }
+}
+
+fn update_cradle_control(
+    cradle: Res<Cradle>,
+    ship_query: Query<&Transform, (With<Ship>, Without<CradleControl>)>,
+    mut control_query: Query<(&mut Transform, &mut Interactable), With<CradleControl>>,
+) {
+    let Ok(ship_tf) = ship_query.single() else { return };
+    let Ok((mut transform, mut interactable)) = control_query.single_mut() else { return };
+    transform.translation = ship_tf.translation + ship_tf.rotation * CRADLE_CONTROL;
+    let kind = if cradle.state == DockState::Afloat {
+        InteractKind::RecoverSub
+    } else {
+        InteractKind::LaunchSub
+    };
+    if interactable.kind != kind {
+        interactable.kind = kind;
+    }
+}
+
+/// Docks or frees the sub to match a loaded save.
+fn restore_dock(mut restore: ResMut<RestoreDock>, mut cradle: ResMut<Cradle>) {
+    let Some(docked) = restore.0.take() else { return };
+    cradle.state = if docked { DockState::Docked } else { DockState::Afloat };
+    cradle.progress = 0.0;
+}
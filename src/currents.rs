@@ -0,0 +1,99 @@
+//! Ocean currents – sampled 3D flow field, authored per region and depth in RON.
+//!
+//! Pushes the swimmer, sub, boids and marine snow, and drags the ship.
+//! Edit assets/currents.ron; missing or invalid file falls back to still water.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+const CURRENTS_PATH: &str = "assets/currents.ron";
+
+/// Flow at one depth (m below sea level). Layers are interpolated linearly.
+#[derive(Serialize, Deserialize, Clone, Copy, Default)]
+pub struct CurrentLayer {
+    pub depth: f32,
+    pub velocity: [f32; 3],
+}
+
+/// A circular region with its own depth profile. Blends into the background toward its edge.
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct CurrentRegion {
+    pub name: String,
+    pub center: [f32; 2],
+    pub radius: f32,
+    pub layers: Vec<CurrentLayer>,
+}
+
+/// Current field resource. `sample` gives water velocity (m/s) at any world point.
+#[derive(Resource, Serialize, Deserialize, Clone, Default)]
+pub struct OceanCurrents {
+    /// Depth profile used everywhere outside regions.
+    #[serde(default)]
+    pub background: Vec<CurrentLayer>,
+    #[serde(default)]
+    pub regions: Vec<CurrentRegion>,
+}
+
+impl OceanCurrents {
+    fn load() -> Self {
+        let Ok(s) = std::fs::read_to_string(CURRENTS_PATH) else {
+            bevy::log::warn!("No current field at {}; water is still", CURRENTS_PATH);
+            return Self::default();
+        };
+        match ron::from_str::<OceanCurrents>(&s) {
+            Ok(mut currents) => {
+                currents.background.sort_by(|a, b| a.depth.total_cmp(&b.depth));
+                for region in currents.regions.iter_mut() {
+                    region.layers.sort_by(|a, b| a.depth.total_cmp(&b.depth));
+                }
+                currents
+            }
+            Err(e) => {
+                bevy::log::warn!("Failed to parse {}: {}", CURRENTS_PATH, e);
+                Self::default()
+            }
+        }
+    }
+
+    /// Water velocity (m/s) at world position. Zero above the still-water level `sea_level`.
+    pub fn sample(&self, pos: Vec3, sea_level: f32) -> Vec3 {
+        let depth = sea_level - pos.y;
+        if depth < 0.0 {
+            return Vec3::ZERO;
+        }
+        let mut velocity = profile_at(&self.background, depth);
+        for region in &self.regions {
+            let offset = Vec2::new(pos.x, pos.z) - Vec2::from_array(region.center);
+            let t = (offset.length() / region.radius.max(0.01)).min(1.0);
+            let weight = (1.0 - t) * (1.0 - t);
+            if weight > 0.0 {
+                velocity = velocity.lerp(profile_at(&region.layers, depth), weight);
+            }
+        }
+        velocity
+    }
+}
+
+/// Linear interpolation through depth-sorted layers, clamped at both ends.
+fn profile_at(layers: &[CurrentLayer], depth: f32) -> Vec3 {
+    let Some(first) = layers.first() else { return Vec3::ZERO };
+    if depth <= first.depth {
+        return Vec3::from_array(first.velocity);
+    }
+    for pair in layers.windows(2) {
+        let (a, b) = (&pair[0], &pair[1]);
+        if depth <= b.depth {
+            let t = (depth - a.depth) / (b.depth - a.depth).max(0.001);
+            return Vec3::from_array(a.velocity).lerp(Vec3::from_array(b.velocity), t);
+        }
+    }
+    Vec3::from_array(layers[layers.len() - 1].velocity)
+}
+
+pub struct CurrentsPlugin;
+
+impl Plugin for CurrentsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(OceanCurrents::load());
+    }
+}
@@ -1,4 +1,9 @@
-//! Submersible - drivable, collidable, oxygen drain underwater.
+//! Submersible - dynamic body floated by its displacement and ballast (see ballast), driven
+//! by thrusters against hydrodynamic drag. Oxygen drains underwater (faster through a leaking hull).
+//! The bell refills surfaced, fast from the ship's oxygen tanks when alongside. Empty, the
+//! pilot suffocates; swimmers near the bell breathe from its reserve (the "safe zone").
+
+use std::f32::consts::PI;
 
 use bevy::gltf::GltfAssetLabel;
 use bevy::input::mouse::AccumulatedMouseMotion;
@@ -7,30 +12,108 @@ use bevy::scene::SceneRoot;
 use bevy::ui::{AlignItems, FlexDirection, JustifyContent};
 
 use bevy_rapier3d::prelude::*;
+use crate::ballast::Ballast;
+use crate::currents::OceanCurrents;
 use crate::game_state::GameState;
+use crate::character::{CharacterOxygen, CharacterVelocity, MarineCharacter};
 use crate::interaction::{Interactable, InteractKind};
 use crate::settings::InputBindings;
+use crate::ship::{Ship, GRAVITY, WATER_DENSITY};
+use crate::ship_stores::ShipStores;
+use crate::sub_hull::{SubHull, SubTier};
+use crate::sub_power::{SubBattery, SubFloodlight};
+use crate::tide::Tide;
 use crate::ocean::OceanSolver;
-use crate::player::{PlayerMode, VEHICLE_ENTER_RANGE};
-use crate::world::{MAP_SCALE_FROM_LEGACY, SPAWN_ISLAND_X, SPAWN_ISLAND_Z};
+use crate::player::{put_camera_on_character, PlayerCamera, PlayerMode, VEHICLE_ENTER_RANGE};
+use crate::wake::WakeSource;
+use crate::world::{
+    character_respawn_position, MAP_SCALE_FROM_LEGACY, SPAWN_ISLAND_X, SPAWN_ISLAND_Z,
+};
 
 const SHIP_ANCHOR_OFFSET: Vec3 = Vec3::new(3.0, 0.0, -2.0);
 /// Sub spawns in water near ship (stern).
 const SUB_OFFSET_FROM_SHIP: Vec3 = Vec3::new(0.0, -4.0, -25.0);
 
+/// Model scale. The collider is sized in world metres and divided back out.
+const SUB_SCALE: f32 = 4.0;
+
+/// Pressure hull height (m): upright cylinder, hatch on top.
+pub const SUB_HEIGHT: f32 = 4.0;
+
+/// Pressure hull radius (m).
+const SUB_RADIUS: f32 = 1.25;
+
+/// Water the sub displaces fully submerged (m³): the collider's volume.
+const SUB_VOLUME: f32 = PI * SUB_RADIUS * SUB_RADIUS * SUB_HEIGHT;
+
+/// Mass (kg) with the ballast tanks dry and the shot weight hung: slightly light, so she
+/// floats with her hatch out until flooded down.
+const SUB_DRY_MASS: f32 = 19_500.0;
+
+/// Centre of buoyancy above the centre of mass (m). Keeps her upright and sets how far
+/// the trim tanks can pitch her.
+const METACENTRIC_HEIGHT: f32 = 0.5;
+
+/// Hydrodynamic drag, hull space: x lateral, y vertical, z fore-aft.
+/// Linear (N per m/s) for settling at low speed, quadratic (N per (m/s)²) for cruising.
+const LINEAR_DRAG: Vec3 = Vec3::new(2000.0, 2000.0, 400.0);
+const QUADRATIC_DRAG: Vec3 = Vec3::new(4000.0, 4000.0, 800.0);
+
+/// How quickly the body rates follow the helm (1/s).
+const ATTITUDE_RESPONSE: f32 = 4.0;
+
+/// Roll rate (rad/s) at full roll input.
+const ROLL_RATE: f32 = 0.8;
+
+/// Stabiliser: rate (rad/s) commanded per radian of pitch or roll off level.
+const LEVEL_GAIN: f32 = 1.0;
+
+/// Extra oxygen drain through a hull at zero integrity, as a multiple of the base rate.
+/// Scales with damage squared, like the ship's leaks.
+const LEAK_OXYGEN_FACTOR: f32 = 4.0;
+
+/// Sub this close (m) under the surface has its hatch open to the air.
+const SNORKEL_DEPTH: f32 = 3.0;
+
+/// Bell air per second let in through the open hatch.
+const SURFACE_REFILL_RATE: f32 = 4.0;
+
+/// Bell air per second charged from the ship's oxygen tanks, surfaced alongside.
+const TANK_REFILL_RATE: f32 = 25.0;
+
+/// Sub within this distance (m) of the ship can take the air hose.
+const AIR_HOSE_RANGE: f32 = 25.0;
+
+/// Pilot's own oxygen lost per second once the bell is empty.
+const SUFFOCATION_RATE: f32 = 4.0;
+
+/// Swimmer within this distance (m) of the bell can breathe from it.
+const BELL_AIR_RANGE: f32 = 12.0;
+
+/// Bell air per second a swimmer tops up from.
+const SWIMMER_TOPUP_RATE: f32 = 15.0;
+
 #[derive(Component)]
 pub struct Submersible {
+    /// Main thruster force (N).
     pub drive_power: f32,
     pub turn_speed: f32,
-    pub ascend_speed: f32,
+    /// Vertical thruster force (N).
+    pub vertical_thrust: f32,
     pub current_throttle: f32,
     pub current_steering: f32,
     pub current_vertical: f32,
+    /// Yaw rate from mouse look (rad/s), sampled per frame and held across fixed steps.
+    pub current_look: f32,
+    /// Pitch rate from mouse look (rad/s), nose up positive.
+    pub current_pitch: f32,
+    /// -1 = roll to port, 1 = roll to starboard.
+    pub current_roll: f32,
+    /// Attitude stabiliser (O): levels pitch and roll when the pilot lets go of them.
+    /// Off, the sub holds whatever attitude she's put in, bar the hull's slow righting.
+    pub stabilise: bool,
 }
 
-#[derive(Component)]
-pub struct SubmersibleVelocity(pub Vec3);
-
 /// Oxygen bar fill node – width updated by update_oxygen_ui.
 #[derive(Component)]
 struct OxygenBarFill;
@@ -57,19 +140,45 @@ impl Plugin for DivingBellPlugin {
                 Update,
                 (
                     diving_bell_oxygen.run_if(in_state(GameState::Playing)),
-                    submersible_input
+                    pilot_breathing
+                        .after(diving_bell_oxygen)
                         .run_if(in_state(GameState::Playing))
                         .run_if(|mode: Res<PlayerMode>| mode.in_submersible),
-                    submersible_movement.run_if(in_state(GameState::Playing)),
-                    submersible_mouse_look
+                    swimmer_bell_air
+                        .after(diving_bell_oxygen)
+                        .run_if(in_state(GameState::Playing))
+                        .run_if(|mode: Res<PlayerMode>| !mode.in_vehicle()),
+                    submersible_input
                         .run_if(in_state(GameState::Playing))
                         .run_if(|mode: Res<PlayerMode>| mode.in_submersible),
+                    submersible_mouse_look.run_if(in_state(GameState::Playing)),
                     update_oxygen_ui.run_if(in_state(GameState::Playing)),
                 ),
+            )
+            .add_systems(
+                FixedUpdate,
+                submersible_movement
+                    .before(PhysicsSet::SyncBackend)
+                    .run_if(in_state(GameState::Playing)),
             );
     }
 }
 
+/// Where the sub starts, off the ship's stern at Safe Island, and where a replacement waits.
+pub fn sub_spawn_transform() -> Transform {
+    Transform::from_xyz(
+        SPAWN_ISLAND_X + SHIP_ANCHOR_OFFSET.x * MAP_SCALE_FROM_LEGACY + SUB_OFFSET_FROM_SHIP.x,
+        SUB_OFFSET_FROM_SHIP.y,
+        SPAWN_ISLAND_Z + SHIP_ANCHOR_OFFSET.z * MAP_SCALE_FROM_LEGACY + SUB_OFFSET_FROM_SHIP.z,
+    )
+    .with_scale(Vec3::splat(SUB_SCALE))
+}
+
+/// Near enough the surface (hatch or snorkel above water) to breathe and charge.
+pub fn is_surfaced(ocean: &OceanSolver, position: Vec3) -> bool {
+    position.y >= ocean.surface_at(position).position.y - SNORKEL_DEPTH
+}
+
 fn spawn_diving_bell(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
@@ -84,34 +193,45 @@ fn spawn_diving_bell(
             ..default()
         },
         Transform::from_xyz(0.0, 0.0, 4.0),
+        SubFloodlight,
     )).id();
 
     let sub_id = commands
         .spawn((
-            RigidBody::KinematicVelocityBased,
-            Collider::cylinder(3.0, 2.5),
-            GravityScale(0.0),
+            RigidBody::Dynamic,
+            Collider::cylinder(SUB_HEIGHT * 0.5 / SUB_SCALE, SUB_RADIUS / SUB_SCALE),
+            ColliderMassProperties::Mass(SUB_DRY_MASS),
+            GravityScale(1.0),
+            Damping {
+                linear_damping: 0.0,
+                angular_damping: 1.0,
+            },
+            ExternalForce::default(),
             Velocity::default(),
+            ReadMassProperties::default(),
             SceneRoot(scene),
-            Transform::from_xyz(
-                SPAWN_ISLAND_X + SHIP_ANCHOR_OFFSET.x * MAP_SCALE_FROM_LEGACY + SUB_OFFSET_FROM_SHIP.x,
-                SUB_OFFSET_FROM_SHIP.y,
-                SPAWN_ISLAND_Z + SHIP_ANCHOR_OFFSET.z * MAP_SCALE_FROM_LEGACY + SUB_OFFSET_FROM_SHIP.z,
-            ).with_scale(Vec3::splat(4.0)),
+            sub_spawn_transform(),
             Submersible {
-            drive_power: 15.0,
+            drive_power: 16_000.0,
             turn_speed: 1.2,
-            ascend_speed: 8.0,
+            vertical_thrust: 8_000.0,
             current_throttle: 0.0,
             current_steering: 0.0,
             current_vertical: 0.0,
+            current_look: 0.0,
+            current_pitch: 0.0,
+            current_roll: 0.0,
+            stabilise: true,
         },
-        SubmersibleVelocity(Vec3::ZERO),
+        WakeSource::new(2.5, 0.2),
         DivingBell {
             max_oxygen: 100.0,
             current_oxygen: 100.0,
             oxygen_drain_rate: 2.0,
         },
+        SubHull::bundle(SubTier::Coastal),
+        SubBattery::default(),
+        Ballast::default(),
         Interactable {
             kind: InteractKind::EnterSubmersible,
             range: VEHICLE_ENTER_RANGE,
@@ -176,38 +296,154 @@ fn update_oxygen_ui(
     }
 }
 
+/// Drains underwater. Surfaced, the hatch lets air in; alongside the ship the hose charges
+/// the bell from the oxygen tanks in her stores.
 fn diving_bell_oxygen(
     ocean: Res<OceanSolver>,
-    mut query: Query<(&Transform, &mut DivingBell)>,
+    mut query: Query<(&Transform, &mut DivingBell, &SubHull)>,
+    mut ship_query: Query<(&Transform, &mut ShipStores), (With<Ship>, Without<DivingBell>)>,
     time: Res<Time>,
 ) {
-    for (transform, mut bell) in query.iter_mut() {
-        let wave_height = ocean.wave_height_at(transform.translation);
-        if transform.translation.y < wave_height {
-            bell.current_oxygen = (bell.current_oxygen - bell.oxygen_drain_rate * time.delta_secs()).max(0.0);
+    let dt = time.delta_secs();
+    for (transform, mut bell, hull) in query.iter_mut() {
+        if !is_surfaced(&ocean, transform.translation) {
+            let damage = hull.damage_fraction();
+            let drain = bell.oxygen_drain_rate * (1.0 + LEAK_OXYGEN_FACTOR * damage * damage);
+            bell.current_oxygen = (bell.current_oxygen - drain * dt).max(0.0);
+            continue;
         }
+        let room = bell.max_oxygen - bell.current_oxygen;
+        let mut refill = (SURFACE_REFILL_RATE * dt).min(room);
+        if let Some((ship_tf, mut stores)) = ship_query.iter_mut().next() {
+            if ship_tf.translation.distance(transform.translation) <= AIR_HOSE_RANGE {
+                refill += stores.draw_oxygen((TANK_REFILL_RATE * dt).min(room - refill));
+            }
+        }
+        bell.current_oxygen += refill;
+    }
+}
+
+/// The pilot breathes the bell's air. Once it's gone they suffocate on what's in their lungs,
+/// and black out to wake on Safe Island.
+fn pilot_breathing(
+    mut commands: Commands,
+    mut mode: ResMut<PlayerMode>,
+    time: Res<Time>,
+    bell_query: Query<&DivingBell>,
+    mut character_query: Query<
+        (Entity, &mut Transform, &mut CharacterOxygen, &mut CharacterVelocity),
+        With<MarineCharacter>,
+    >,
+    camera_query: Query<Entity, With<PlayerCamera>>,
+) {
+    let Ok(bell) = bell_query.single() else { return };
+    let Ok((char_id, mut transform, mut oxygen, mut vel)) = character_query.single_mut() else {
+        return;
+    };
+    let dt = time.delta_secs();
+    if bell.current_oxygen > 0.0 {
+        oxygen.current = (oxygen.current + oxygen.refill_rate * dt).min(oxygen.max);
+        return;
+    }
+    oxygen.current = (oxygen.current - SUFFOCATION_RATE * dt).max(0.0);
+    if oxygen.current > 0.0 {
+        return;
+    }
+    bevy::log::warn!("Blacked out in the sub with the bell's air gone");
+    mode.in_submersible = false;
+    transform.translation = character_respawn_position();
+    oxygen.current = oxygen.max;
+    vel.0 = Vec3::ZERO;
+    if let Some(cam_id) = camera_query.iter().next() {
+        put_camera_on_character(&mut commands, cam_id, char_id);
     }
 }
 
-fn submersible_movement(
-    mut query: Query<(&Submersible, &mut SubmersibleVelocity, &mut Transform, &mut Velocity)>,
+/// A swimmer within reach of the bell tops up their oxygen from its reserve.
+fn swimmer_bell_air(
     time: Res<Time>,
+    mut bell_query: Query<(&Transform, &mut DivingBell)>,
+    mut character_query: Query<(&Transform, &mut CharacterOxygen), Without<DivingBell>>,
+) {
+    let Ok((bell_tf, mut bell)) = bell_query.single_mut() else { return };
+    let Ok((char_tf, mut oxygen)) = character_query.single_mut() else { return };
+    if char_tf.translation.distance(bell_tf.translation) > BELL_AIR_RANGE {
+        return;
+    }
+    let take = (SWIMMER_TOPUP_RATE * time.delta_secs())
+        .min(oxygen.max - oxygen.current)
+        .min(bell.current_oxygen);
+    oxygen.current += take;
+    bell.current_oxygen -= take;
+}
+
+/// Buoyancy from the water displaced, less the ballast, acting above the centre of mass so
+/// she rights herself; the trim tanks pitch her against that. Thrusters push against drag
+/// relative to the water, so an idle sub drifts with the current.
+pub(crate) fn submersible_movement(
+    ocean: Res<OceanSolver>,
+    currents: Res<OceanCurrents>,
+    tide: Res<Tide>,
+    mut query: Query<(
+        &Submersible,
+        &Ballast,
+        &SubBattery,
+        &Transform,
+        &Velocity,
+        &ReadMassProperties,
+        &mut ExternalForce,
+    )>,
 ) {
-    const WATER_DRAG: f32 = 0.95;    // decay when idle; sub holds depth/position (neutral buoyancy)
+    for (sub, ballast, battery, transform, velocity, mass, mut ext_force) in query.iter_mut() {
+        let rotation = transform.rotation;
+        let surface = ocean.surface_at(transform.translation).position.y;
+        let submerged =
+            ((surface - transform.translation.y) / SUB_HEIGHT + 0.5).clamp(0.0, 1.0);
+        let buoyancy = Vec3::Y * WATER_DENSITY * GRAVITY * SUB_VOLUME * submerged;
+        let mut force = buoyancy - Vec3::Y * ballast.weight();
+        let mut torque = (rotation * Vec3::Y * METACENTRIC_HEIGHT).cross(buoyancy)
+            - rotation * Vec3::X * ballast.trim_moment();
 
-    for (sub, mut vel, transform, mut rb_vel) in query.iter_mut() {
-        let forward = transform.forward();
-        vel.0 += forward * sub.drive_power * sub.current_throttle * time.delta_secs();
-        vel.0.y += sub.ascend_speed * sub.current_vertical * time.delta_secs();
+        let current = currents.sample(transform.translation, tide.level);
+        let relative = rotation.inverse() * (velocity.linvel - current);
+        let drag = -(LINEAR_DRAG * relative + QUADRATIC_DRAG * relative.abs() * relative);
+        force += rotation * drag * submerged.max(0.1);
 
-        // Neutral buoyancy: no input = no sink/rise. Sub is trim-able and holds depth.
-        vel.0 *= WATER_DRAG;
+        // A flat battery leaves the thrusters dead: she drifts, and rises or sinks on her ballast.
+        let power = if battery.is_flat() { 0.0 } else { 1.0 };
+        force += rotation * Vec3::NEG_Z * sub.drive_power * sub.current_throttle * power;
+        force += rotation * Vec3::Y * sub.vertical_thrust * sub.current_vertical * power;
+        torque += rotation * attitude_torque(sub, transform, velocity, mass, power);
 
-        rb_vel.linvel = vel.0;
-        rb_vel.angvel = Vec3::new(0.0, sub.turn_speed * sub.current_steering, 0.0);
+        ext_force.force = force;
+        ext_force.torque = torque;
     }
 }
 
+/// Body-frame torque steering the body rates toward the helm: pitch and yaw from the mouse
+/// and A/D, roll from the roll keys. With the stabiliser on, an axis left alone is driven
+/// back to level; off, it is held still.
+fn attitude_torque(
+    sub: &Submersible,
+    transform: &Transform,
+    velocity: &Velocity,
+    mass: &ReadMassProperties,
+    power: f32,
+) -> Vec3 {
+    let pitch = transform.forward().y.clamp(-1.0, 1.0).asin();
+    let roll = transform.right().y.clamp(-1.0, 1.0).asin();
+    let level = if sub.stabilise { LEVEL_GAIN } else { 0.0 };
+    let pitch_rate = if sub.current_pitch != 0.0 { sub.current_pitch } else { -level * pitch };
+    let roll_rate = if sub.current_roll != 0.0 { -ROLL_RATE * sub.current_roll } else { -level * roll };
+    let target = Vec3::new(
+        pitch_rate,
+        sub.turn_speed * sub.current_steering + sub.current_look,
+        roll_rate,
+    ) * power;
+    let body_rate = transform.rotation.inverse() * velocity.angvel;
+    mass.get().principal_inertia * ATTITUDE_RESPONSE * (target - body_rate)
+}
+
 fn submersible_input(
     keyboard: Res<ButtonInput<KeyCode>>,
     bindings: Res<InputBindings>,
@@ -235,24 +471,40 @@ fn submersible_input(
         } else {
             0.0
         };
+        sub.current_roll = if keyboard.pressed(bindings.roll_right) {
+            1.0
+        } else if keyboard.pressed(bindings.roll_left) {
+            -1.0
+        } else {
+            0.0
+        };
+        if keyboard.just_pressed(bindings.stabiliser) {
+            sub.stabilise = !sub.stabilise;
+            bevy::log::info!("Attitude stabiliser {}", if sub.stabilise { "on" } else { "off" });
+        }
     }
 }
 
-/// Mouse look via angular velocity (matches ship; avoids Rapier overwriting Transform).
+/// Mouse look as yaw and pitch rates for the helm (avoids Rapier overwriting Transform).
+/// The camera rides the sub, so the view follows her attitude.
 fn submersible_mouse_look(
     mouse_motion: Res<AccumulatedMouseMotion>,
+    mode: Res<PlayerMode>,
     time: Res<Time>,
-    mut query: Query<&mut Velocity, With<Submersible>>,
+    mut query: Query<&mut Submersible>,
 ) {
-    let delta_x = mouse_motion.delta.x;
-    if delta_x == 0.0 {
-        return;
-    }
     const SENSITIVITY: f32 = 0.002;
     let dt = time.delta_secs().max(0.001);
-    let ang = -delta_x * SENSITIVITY / dt;
-    for mut rb_vel in query.iter_mut() {
-        rb_vel.angvel.y += ang;
+    let (yaw, pitch) = if mode.in_submersible {
+        (
+            -mouse_motion.delta.x * SENSITIVITY / dt,
+            -mouse_motion.delta.y * SENSITIVITY / dt,
+        )
+    } else {
+        (0.0, 0.0)
+    };
+    for mut sub in query.iter_mut() {
+        sub.current_look = yaw;
+        sub.current_pitch = pitch;
     }
 }
-
@@ -5,10 +5,11 @@
 use bevy::prelude::*;
 
 use crate::character::MarineCharacter;
+use crate::currents::OceanCurrents;
 use crate::diving_bell::Submersible;
 use crate::ship::Ship;
 use crate::game_state::GameState;
-use crate::ocean::SEA_LEVEL;
+use crate::tide::Tide;
 use crate::world::{MAP_FLOOR_Y, MAP_SCALE_FROM_LEGACY};
 
 /// Small schooling fish. Boids algorithm: cohesion, separation, alignment, flee.
@@ -41,12 +42,10 @@ impl Plugin for FaunaPlugin {
         app.init_resource::<BoidSnapshot>()
             .add_systems(Startup, spawn_boid_schools)
             .add_systems(
-                Update,
-                copy_boid_snapshot.run_if(in_state(GameState::Playing)),
-            )
-            .add_systems(
-                PostUpdate,
-                boids_steering.run_if(in_state(GameState::Playing)),
+                FixedUpdate,
+                (copy_boid_snapshot, boids_steering)
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
             );
     }
 }
@@ -69,6 +68,8 @@ fn copy_boid_snapshot(
 fn boids_steering(
     time: Res<Time>,
     snapshot: Res<BoidSnapshot>,
+    currents: Res<OceanCurrents>,
+    tide: Res<Tide>,
     character_query: Query<&Transform, With<MarineCharacter>>,
     sub_query: Query<&Transform, With<Submersible>>,
     mut boid_query: Query<(&mut Boid, &mut Transform), (With<Boid>, Without<Submersible>, Without<Ship>, Without<MarineCharacter>)>,
@@ -79,7 +80,7 @@ fn boids_steering(
     let threat_positions: Vec<Vec3> = character_query
         .iter()
         .chain(sub_query.iter())
-        .filter(|t| t.translation.y < SEA_LEVEL - 0.5)
+        .filter(|t| t.translation.y < tide.level - 0.5)
         .map(|t| t.translation)
         .collect();
 
@@ -158,9 +159,9 @@ fn boids_steering(
             boid.velocity += Vec3::new(0.1, 0.0, 0.1) * (school_id as f32 * 0.1).sin();
         }
 
-        // Keep in water column
-        let mut new_pos = pos + boid.velocity * dt;
-        new_pos.y = new_pos.y.clamp(MAP_FLOOR_Y + 2.0, SEA_LEVEL - 1.0);
+        // Keep in water column. Fish swim relative to the water, so the current carries the school.
+        let mut new_pos = pos + (boid.velocity + currents.sample(pos, tide.level)) * dt;
+        new_pos.y = new_pos.y.clamp(MAP_FLOOR_Y + 2.0, tide.level - 1.0);
 
         transform.translation = new_pos;
         transform.rotation = quat_from_forward(boid.velocity.normalize_or_zero());
@@ -184,7 +185,7 @@ fn spawn_boid_schools(
         ..default()
     });
 
-    // Schools spawn in underwater zones (Y < SEA_LEVEL). 10–30 cm fish.
+    // Schools spawn in underwater zones (Y < MEAN_SEA_LEVEL). 10–30 cm fish.
     let school_centers = [
         Vec3::new(80.0 * MAP_SCALE_FROM_LEGACY, -15.0, 120.0 * MAP_SCALE_FROM_LEGACY),
         Vec3::new(-60.0 * MAP_SCALE_FROM_LEGACY, -25.0, -80.0 * MAP_SCALE_FROM_LEGACY),
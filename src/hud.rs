@@ -0,0 +1,60 @@
+//! HUD panels – the stack of one-line status rows above the oxygen bars, bottom left.
+//!
+//! Each module spawns its row with `spawn_hud_row` at a slot from here, so the stack is laid
+//! out in one place. Ship and sub rows share the low slots: each is only shown aboard its own
+//! vehicle (the ship's hull row also shows swimming, when the sub's is hidden).
+
+use bevy::prelude::*;
+use bevy::ui::{AlignItems, FlexDirection, JustifyContent};
+
+/// Bottom of slot 0 (px), clear of the oxygen bars.
+const FIRST_ROW_BOTTOM: f32 = 88.0;
+
+/// Slot pitch (px): one row of 16 px text plus padding and a gap.
+const ROW_PITCH: f32 = 34.0;
+
+pub const SHIP_HULL_SLOT: usize = 0;
+pub const SAIL_SLOT: usize = 1;
+pub const STORES_SLOT: usize = 2;
+pub const ANCHOR_SLOT: usize = 3;
+pub const AUTOPILOT_SLOT: usize = 4;
+/// Shown in either vehicle, so above both stacks.
+pub const WINCH_SLOT: usize = 5;
+
+pub const SUB_HULL_SLOT: usize = 0;
+pub const BALLAST_SLOT: usize = 1;
+
+/// Spawns a hidden row at `slot` with one text line tagged `marker`. Returns the row and
+/// the text entity.
+pub fn spawn_hud_row(
+    commands: &mut Commands,
+    slot: usize,
+    marker: impl Component,
+) -> (Entity, Entity) {
+    let text_id = commands
+        .spawn((
+            Text::new(""),
+            TextFont { font_size: 16.0, ..default() },
+            TextColor(Color::srgba(1.0, 1.0, 1.0, 0.95)),
+            marker,
+        ))
+        .id();
+    let root_id = commands
+        .spawn((
+            Node {
+                position_type: bevy::ui::PositionType::Absolute,
+                left: Val::Px(20.0),
+                bottom: Val::Px(FIRST_ROW_BOTTOM + slot as f32 * ROW_PITCH),
+                flex_direction: FlexDirection::Row,
+                justify_content: JustifyContent::FlexStart,
+                align_items: AlignItems::Center,
+                padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.1, 0.15, 0.25, 0.9)),
+            Visibility::Hidden,
+        ))
+        .add_child(text_id)
+        .id();
+    (root_id, text_id)
+}
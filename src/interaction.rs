@@ -29,6 +29,8 @@ pub enum InteractKind {
     EnterSubmersible,
     Pickup { item_id: String },
     AttachToWinch { item_id: String },
+    LaunchSub,
+    RecoverSub,
 }
 
 impl InteractKind {
@@ -38,6 +40,8 @@ impl InteractKind {
             InteractKind::EnterSubmersible => "Press E to enter submersible".into(),
             InteractKind::Pickup { item_id } => format!("Press E to pick up {}", item_id),
             InteractKind::AttachToWinch { item_id } => format!("Press E to attach {} to winch", item_id),
+            InteractKind::LaunchSub => "Press E to launch submersible".into(),
+            InteractKind::RecoverSub => "Press E to recover submersible".into(),
         }
     }
 }
@@ -10,19 +10,38 @@ mod artifacts;
 mod audio;
 mod fauna;
 mod settings;
+mod tide;
 mod game_state;
+mod hud;
 mod interaction;
 mod ocean;
+mod ocean_config;
 mod save_load;
 mod ship;
+mod ship_hull;
+mod sailing;
+mod ship_stores;
+mod anchor;
+mod autopilot;
 mod diving_bell;
+mod sub_hull;
+mod sub_power;
+mod ballast;
+mod manipulator;
+mod sonar;
+mod cradle;
 mod winch;
+mod wreck;
 mod world;
 mod character;
+mod currents;
 mod player;
 mod islands;
 mod scatter;
 mod marine_snow;
+mod weather;
+mod sim_clock;
+mod wake;
 
 use bevy_rapier3d::prelude::*;
 
@@ -39,16 +58,35 @@ fn main() {
         .insert_resource(DirectionalLightShadowMap { size: 4096 })
         .add_systems(Startup, setup_scene)
         .add_plugins(DefaultPlugins)
-        .add_plugins(RapierPhysicsPlugin::<NoUserData>::default())
+        .insert_resource(TimestepMode::Fixed { dt: sim_clock::SIM_DT, substeps: 1 })
+        .add_plugins(RapierPhysicsPlugin::<NoUserData>::default().in_fixed_schedule())
+        .add_plugins(sim_clock::SimClockPlugin)
         .add_plugins(GameStatePlugin)
         .add_plugins(settings::SettingsPlugin)
         .add_plugins(artifacts::ArtifactsPlugin)
         .add_plugins(audio::AudioPlugin)
         .add_plugins(OceanPlugin)
+        .add_plugins(ocean_config::OceanConfigPlugin)
+        .add_plugins(weather::WeatherPlugin)
+        .add_plugins(currents::CurrentsPlugin)
+        .add_plugins(tide::TidePlugin)
+        .add_plugins(wake::WakePlugin)
         .add_plugins(PlayerPlugin)
         .add_plugins(ShipPlugin)
+        .add_plugins(ship_hull::ShipHullPlugin)
+        .add_plugins(sailing::SailingPlugin)
+        .add_plugins(ship_stores::ShipStoresPlugin)
+        .add_plugins(anchor::AnchorPlugin)
+        .add_plugins(autopilot::AutopilotPlugin)
         .add_plugins(DivingBellPlugin)
+        .add_plugins(sub_hull::SubHullPlugin)
+        .add_plugins(sub_power::SubPowerPlugin)
+        .add_plugins(ballast::BallastPlugin)
+        .add_plugins(manipulator::ManipulatorPlugin)
+        .add_plugins(sonar::SonarPlugin)
         .add_plugins(winch::WinchPlugin)
+        .add_plugins(cradle::CradlePlugin)
+        .add_plugins(wreck::WreckPlugin)
         .add_plugins(CharacterPlugin)
         .add_plugins(scatter::ScatterPlugin)
         .add_plugins(marine_snow::MarineSnowPlugin)
@@ -0,0 +1,345 @@
+//! Manipulator arm – a jointed claw on the sub's bow for grabbing artifacts.
+//!
+//! The arm is two Rapier bodies: an upper arm on a motored shoulder (spherical joint to the
+//! sub) and a forearm with the claw on a motored elbow. The pilot aims it with the arrow keys,
+//! extends or folds it (U / Y) and closes the claw (C) on an artifact within reach, which is
+//! then held by a fixed joint as a dynamic body, its weight on the arm. The joint motors have
+//! a torque limit, so loads sag, and the claw won't take anything past its rated load: heavy
+//! artifacts go on the winch hook (E), which can also take whatever the claw is holding.
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+use crate::artifacts::{item_mass, Artifact};
+use crate::ballast::run_pumps;
+use crate::diving_bell::Submersible;
+use crate::game_state::GameState;
+use crate::player::PlayerMode;
+use crate::settings::InputBindings;
+use crate::sim_clock::SIM_DT;
+use crate::sub_power::SubBattery;
+
+/// Shoulder mount on the sub, body space (m): low on the bow, clear of the hull.
+const SHOULDER: Vec3 = Vec3::new(0.0, -1.5, -1.5);
+
+/// Upper arm and forearm lengths (m).
+const UPPER_ARM: f32 = 3.0;
+const FOREARM: f32 = 2.5;
+
+/// Segment masses (kg). The arm is trimmed neutral, so only its load weighs on it.
+const UPPER_ARM_MASS: f32 = 120.0;
+const FOREARM_MASS: f32 = 80.0;
+
+/// Claw tip in forearm body space (m).
+const CLAW_TIP: Vec3 = Vec3::new(0.0, 0.0, -FOREARM * 0.5);
+
+/// An artifact within this distance (m) of the claw tip can be grabbed.
+const CLAW_RANGE: f32 = 1.5;
+
+/// Heaviest load (kg) the claw will close on.
+const CLAW_MAX_LOAD: f32 = 300.0;
+
+/// Joint motor torque limits (N·m): what the arm can hold out against a load.
+const SHOULDER_TORQUE: f32 = 2.0e4;
+const ELBOW_TORQUE: f32 = 1.2e4;
+
+/// Joint motor spring and damping.
+const ARM_STIFFNESS: f32 = 120.0;
+const ARM_DAMPING: f32 = 25.0;
+
+/// Aim and elbow travel (rad/s).
+const AIM_RATE: f32 = 0.8;
+
+/// Shoulder yaw either side, pitch down and up, and elbow fold (rad).
+const YAW_LIMIT: f32 = 1.2;
+const PITCH_LIMITS: [f32; 2] = [-1.2, 0.5];
+const ELBOW_FOLD: f32 = 2.2;
+
+/// Battery charge per second while the arm moves.
+const ARM_DRAW: f32 = 0.1;
+
+/// Claw further than this (m) from the shoulder: the sub has been moved under it (reset,
+/// load), so the arm is put back rather than dragged across the sea by its joints.
+const ARM_SNAP: f32 = 20.0;
+
+/// Arm aim and what the claw holds. On the sub.
+#[derive(Component)]
+pub struct Manipulator {
+    /// Shoulder yaw, positive to port (rad).
+    pub yaw: f32,
+    /// Shoulder pitch, positive up (rad).
+    pub pitch: f32,
+    /// Elbow bend, 0 = straight out, negative = folded down (rad).
+    pub elbow: f32,
+    /// Aim input from the helm, -1..1 each: yaw, pitch, elbow.
+    pub aim_input: Vec3,
+    /// Artifact held in the claw.
+    pub held: Option<Entity>,
+    /// Claw tip, world space, as of the last frame.
+    pub tip: Vec3,
+}
+
+impl Default for Manipulator {
+    fn default() -> Self {
+        Self {
+            yaw: 0.0,
+            pitch: -0.4,
+            elbow: -0.8,
+            aim_input: Vec3::ZERO,
+            held: None,
+            tip: Vec3::ZERO,
+        }
+    }
+}
+
+impl Manipulator {
+    /// Opens the claw. The held artifact, if any, is let go as a free body.
+    pub fn release(&mut self, commands: &mut Commands) -> Option<Entity> {
+        let art_id = self.held.take()?;
+        if let Ok(mut entity) = commands.get_entity(art_id) {
+            entity.remove::<ImpulseJoint>();
+        }
+        Some(art_id)
+    }
+}
+
+/// An arm body and its rest position in sub body space.
+#[derive(Component)]
+struct ArmSegment {
+    rest: Vec3,
+}
+
+/// The forearm, which carries the claw.
+#[derive(Component)]
+struct Claw;
+
+pub struct ManipulatorPlugin;
+
+impl Plugin for ManipulatorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PostStartup, spawn_arm)
+            .add_systems(
+                FixedUpdate,
+                drive_arm
+                    .after(run_pumps)
+                    .before(PhysicsSet::SyncBackend)
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                Update,
+                (
+                    arm_controls.run_if(in_state(GameState::Playing)),
+                    track_claw.run_if(in_state(GameState::Playing)),
+                ),
+            );
+    }
+}
+
+fn spawn_arm(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    sub_query: Query<(Entity, &Transform), With<Submersible>>,
+) {
+    let Ok((sub_id, sub_tf)) = sub_query.single() else { return };
+    let mat = materials.add(StandardMaterial {
+        base_color: Color::srgb(0.85, 0.6, 0.15),
+        metallic: 0.8,
+        perceptual_roughness: 0.4,
+        ..default()
+    });
+    let upper_rest = SHOULDER + Vec3::new(0.0, 0.0, -UPPER_ARM * 0.5);
+    let fore_rest = SHOULDER + Vec3::new(0.0, 0.0, -UPPER_ARM - FOREARM * 0.5);
+
+    let upper_id = commands
+        .spawn((
+            Mesh3d(meshes.add(Cuboid::new(0.35, 0.35, UPPER_ARM))),
+            MeshMaterial3d(mat.clone()),
+            Transform::from_translation(sub_tf.translation + sub_tf.rotation * upper_rest)
+                .with_rotation(sub_tf.rotation),
+            RigidBody::Dynamic,
+            Collider::cuboid(0.175, 0.175, UPPER_ARM * 0.5),
+            Sensor,
+            ColliderMassProperties::Mass(UPPER_ARM_MASS),
+            GravityScale(0.0),
+            Velocity::default(),
+            ArmSegment { rest: upper_rest },
+            ImpulseJoint::new(
+                sub_id,
+                SphericalJointBuilder::new()
+                    .local_anchor1(SHOULDER)
+                    .local_anchor2(Vec3::new(0.0, 0.0, UPPER_ARM * 0.5))
+                    .limits(JointAxis::AngX, PITCH_LIMITS)
+                    .limits(JointAxis::AngY, [-YAW_LIMIT, YAW_LIMIT])
+                    .motor_max_force(JointAxis::AngX, SHOULDER_TORQUE)
+                    .motor_max_force(JointAxis::AngY, SHOULDER_TORQUE)
+                    .motor_max_force(JointAxis::AngZ, SHOULDER_TORQUE),
+            ),
+        ))
+        .id();
+    commands.spawn((
+        Mesh3d(meshes.add(Cuboid::new(0.25, 0.25, FOREARM))),
+        MeshMaterial3d(mat),
+        Transform::from_translation(sub_tf.translation + sub_tf.rotation * fore_rest)
+            .with_rotation(sub_tf.rotation),
+        RigidBody::Dynamic,
+        Collider::cuboid(0.125, 0.125, FOREARM * 0.5),
+        Sensor,
+        ColliderMassProperties::Mass(FOREARM_MASS),
+        GravityScale(0.0),
+        Velocity::default(),
+        ArmSegment { rest: fore_rest },
+        Claw,
+        ImpulseJoint::new(
+            upper_id,
+            RevoluteJointBuilder::new(Vec3::X)
+                .local_anchor1(Vec3::new(0.0, 0.0, -UPPER_ARM * 0.5))
+                .local_anchor2(Vec3::new(0.0, 0.0, FOREARM * 0.5))
+                .limits([-ELBOW_FOLD, 0.0])
+                .motor_max_force(ELBOW_TORQUE),
+        ),
+    ));
+    commands.entity(sub_id).insert(Manipulator::default());
+}
+
+/// Arrow keys aim the shoulder, U / Y work the elbow, C closes or opens the claw. The aim
+/// stops when the pilot leaves.
+fn arm_controls(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<InputBindings>,
+    mode: Res<PlayerMode>,
+    mut sub_query: Query<&mut Manipulator>,
+    claw_query: Query<(Entity, &Transform), With<Claw>>,
+    artifact_query: Query<(Entity, &Transform, &Artifact), (With<Collider>, Without<ChildOf>)>,
+) {
+    let Ok(mut arm) = sub_query.single_mut() else { return };
+    if !mode.in_submersible {
+        arm.aim_input = Vec3::ZERO;
+        return;
+    }
+    let Ok((claw_id, claw_tf)) = claw_query.single() else { return };
+
+    let axis = |plus: KeyCode, minus: KeyCode| match (keyboard.pressed(plus), keyboard.pressed(minus)) {
+        (true, false) => 1.0,
+        (false, true) => -1.0,
+        _ => 0.0,
+    };
+    arm.aim_input = Vec3::new(
+        axis(bindings.arm_left, bindings.arm_right),
+        axis(bindings.arm_up, bindings.arm_down),
+        axis(bindings.arm_extend, bindings.arm_retract),
+    );
+
+    if !keyboard.just_pressed(bindings.claw) {
+        return;
+    }
+    if arm.release(&mut commands).is_some() {
+        bevy::log::info!("Claw open");
+        return;
+    }
+    let tip = claw_tf.translation + claw_tf.rotation * CLAW_TIP;
+    let Some((art_id, art_tf, artifact)) = artifact_query
+        .iter()
+        .filter(|(_, t, _)| t.translation.distance(tip) <= CLAW_RANGE)
+        .min_by(|a, b| {
+            a.1.translation
+                .distance(tip)
+                .total_cmp(&b.1.translation.distance(tip))
+        })
+    else {
+        return;
+    };
+    let mass = item_mass(&artifact.item_id);
+    if mass > CLAW_MAX_LOAD {
+        bevy::log::warn!(
+            "{} is too heavy for the claw ({:.0} kg, rated {:.0} kg); hook it on the winch",
+            artifact.item_id,
+            mass,
+            CLAW_MAX_LOAD
+        );
+        return;
+    }
+    let grip = FixedJointBuilder::new()
+        .local_anchor1(CLAW_TIP)
+        .local_anchor2(art_tf.rotation.inverse() * (tip - art_tf.translation))
+        .local_basis2(art_tf.rotation.inverse() * claw_tf.rotation);
+    commands.entity(art_id).insert((
+        RigidBody::Dynamic,
+        ColliderMassProperties::Mass(mass),
+        Velocity::default(),
+        ImpulseJoint::new(claw_id, grip),
+    ));
+    arm.held = Some(art_id);
+    bevy::log::info!("Claw closed on {}", artifact.item_id);
+}
+
+/// Moves the aim at the pilot's input while the battery lasts, and points the shoulder and
+/// elbow motors at it when it moves.
+fn drive_arm(
+    mut aimed: Local<Option<Vec3>>,
+    mut sub_query: Query<(&mut Manipulator, &mut SubBattery)>,
+    mut joint_query: Query<&mut ImpulseJoint, With<ArmSegment>>,
+) {
+    let Ok((mut arm, mut battery)) = sub_query.single_mut() else { return };
+    let input = arm.aim_input;
+    if input != Vec3::ZERO && battery.draw(ARM_DRAW * SIM_DT) {
+        let step = input * AIM_RATE * SIM_DT;
+        arm.yaw = (arm.yaw + step.x).clamp(-YAW_LIMIT, YAW_LIMIT);
+        arm.pitch = (arm.pitch + step.y).clamp(PITCH_LIMITS[0], PITCH_LIMITS[1]);
+        arm.elbow = (arm.elbow + step.z).clamp(-ELBOW_FOLD, 0.0);
+    }
+    let aim = Vec3::new(arm.yaw, arm.pitch, arm.elbow);
+    if *aimed == Some(aim) {
+        return;
+    }
+    *aimed = Some(aim);
+    for mut joint in joint_query.iter_mut() {
+        match &mut joint.data {
+            TypedJoint::SphericalJoint(shoulder) => {
+                shoulder
+                    .set_motor_position(JointAxis::AngX, arm.pitch, ARM_STIFFNESS, ARM_DAMPING)
+                    .set_motor_position(JointAxis::AngY, arm.yaw, ARM_STIFFNESS, ARM_DAMPING)
+                    .set_motor_position(JointAxis::AngZ, 0.0, ARM_STIFFNESS, ARM_DAMPING);
+            }
+            TypedJoint::RevoluteJoint(elbow) => {
+                elbow.set_motor_position(arm.elbow, ARM_STIFFNESS, ARM_DAMPING);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Keeps the claw tip current, forgets a held artifact that's gone (delivered, picked up),
+/// and puts the arm back on the sub if she's been moved out from under it.
+fn track_claw(
+    mut commands: Commands,
+    mut sub_query: Query<(&Transform, &mut Manipulator), With<Submersible>>,
+    mut segment_query: Query<
+        (&mut Transform, &mut Velocity, &ArmSegment, Has<Claw>),
+        Without<Submersible>,
+    >,
+    held_query: Query<&ImpulseJoint, Without<ArmSegment>>,
+) {
+    let Ok((sub_tf, mut arm)) = sub_query.single_mut() else { return };
+    if arm.held.is_some_and(|art_id| held_query.get(art_id).is_err()) {
+        arm.held = None;
+    }
+    let shoulder = sub_tf.translation + sub_tf.rotation * SHOULDER;
+    let snapped = segment_query
+        .iter()
+        .any(|(t, _, _, _)| t.translation.distance(shoulder) > ARM_SNAP);
+    if snapped {
+        arm.release(&mut commands);
+    }
+    for (mut transform, mut velocity, segment, is_claw) in segment_query.iter_mut() {
+        if snapped {
+            *transform = Transform::from_translation(sub_tf.translation + sub_tf.rotation * segment.rest)
+                .with_rotation(sub_tf.rotation);
+            *velocity = Velocity::zero();
+        }
+        if is_claw {
+            arm.tip = transform.translation + transform.rotation * CLAW_TIP;
+        }
+    }
+}
@@ -3,6 +3,7 @@
 
 use bevy::prelude::*;
 
+use crate::currents::OceanCurrents;
 use crate::ocean::OceanSolver;
 use crate::player::PlayerCamera;
 
@@ -91,6 +92,7 @@ fn spawn_marine_snow(
 
 fn update_marine_snow(
     ocean: Res<OceanSolver>,
+    currents: Res<OceanCurrents>,
     time: Res<Time>,
     camera_query: Query<&GlobalTransform, With<PlayerCamera>>,
     mut root_query: Query<(&mut Transform, &mut Visibility), With<MarineSnowRoot>>,
@@ -117,6 +119,8 @@ fn update_marine_snow(
 
     let dt = time.delta_secs();
     let mut rng = FastNoise::new(0);
+    // Root is unrotated at the camera, so local offsets are world-space: drift with the current.
+    let current = currents.sample(cam_pos, ocean.sea_level);
 
     for (mut tf, mut particle) in particle_query.iter_mut() {
         particle.velocity += Vec3::new(
@@ -126,7 +130,7 @@ fn update_marine_snow(
         ) * DRIFT_SPEED * 0.3;
         particle.velocity = particle.velocity.clamp_length_max(DRIFT_SPEED * 2.0);
 
-        tf.translation += particle.velocity * dt;
+        tf.translation += (particle.velocity + current) * dt;
 
         if tf.translation.length() > RECYCLE_RADIUS {
             tf.translation = random_in_sphere(&mut rng, SPHERE_RADIUS * 0.8);
@@ -147,14 +151,15 @@ fn random_in_sphere(rng: &mut FastNoise, radius: f32) -> Vec3 {
     )
 }
 
-/// Simple deterministic RNG for reproducible snow.
-struct FastNoise(u32);
+/// Simple deterministic RNG for reproducible snow. Also drives bow spray (wake.rs).
+#[derive(Default)]
+pub(crate) struct FastNoise(u32);
 
 impl FastNoise {
-    fn new(seed: u32) -> Self {
+    pub(crate) fn new(seed: u32) -> Self {
         Self(seed)
     }
-    fn next(&mut self) -> f32 {
+    pub(crate) fn next(&mut self) -> f32 {
         self.0 = self.0.wrapping_mul(1103515245).wrapping_add(12345);
         ((self.0 >> 16) & 0x7FFF) as f32 / 32767.0
     }
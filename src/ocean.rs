@@ -1,14 +1,25 @@
 //! Gerstner wave ocean solver - CPU-side buoyancy and water mesh.
+//!
+//! The water mesh is a camera-centred clipmap: nested square rings, each level with
+//! twice the cell size of the one inside it. Waves too short for a level fade out with
+//! distance instead of aliasing.
 
 use bevy::prelude::*;
 use bevy::asset::RenderAssetUsages;
-use bevy::image::{Image, ImageSampler, ImageSamplerDescriptor};
-use bevy::mesh::VertexAttributeValues;
+use bevy::camera::visibility::NoFrustumCulling;
+use bevy::image::{Image, ImageAddressMode, ImageSampler, ImageSamplerDescriptor};
+use bevy::mesh::{Indices, PrimitiveTopology, VertexAttributeValues};
 use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
 
-/// Resource storing the dynamic water mesh handle for per-frame vertex updates.
+use crate::game_state::GameState;
+use crate::ocean_config::{ActiveOceanConfig, OceanConfig};
+use crate::player::PlayerCamera;
+use crate::sim_clock::SimClock;
+use crate::wake::{RippleField, RIPPLE_CELL};
+
+/// Shared water material, retinted when the ocean preset changes.
 #[derive(Resource)]
-pub struct WaterMeshHandle(pub Handle<Mesh>);
+pub struct WaterMaterial(pub Handle<StandardMaterial>);
 
 /// Wave parameters for one Gerstner layer.
 #[derive(Clone)]
@@ -40,38 +51,146 @@ impl GerstnerWave {
     }
 }
 
-/// Sea level (Y). Water surface oscillates around this.
-pub const SEA_LEVEL: f32 = -2.0;
+/// Mean sea level (Y) over a tide cycle. Live level is `tide::Tide::level`.
+pub const MEAN_SEA_LEVEL: f32 = -2.0;
+
+/// Fixed-point iterations used to invert the horizontal displacement in `surface_at`.
+const SURFACE_INVERSE_ITERATIONS: usize = 4;
+
+/// One evaluated point on the displaced ocean surface.
+#[derive(Clone, Copy, Debug)]
+pub struct OceanSample {
+    /// Displaced surface point (world space).
+    pub position: Vec3,
+    /// Unit surface normal.
+    pub normal: Vec3,
+    /// Orbital velocity of the water particle at the surface (m/s).
+    pub velocity: Vec3,
+    /// Wake foam coverage 0..1 from the ripple layer.
+    pub foam: f32,
+}
 
 /// Ocean solver resource - wave height at any position.
 #[derive(Resource)]
 pub struct OceanSolver {
     pub time: f32,
+    /// Calm-sea swell from the active `OceanConfig`. Weather rotates and scales it into `waves`.
+    pub base_waves: Vec<GerstnerWave>,
+    /// Still-water level (Y) the waves oscillate around. Driven by the tide.
+    pub sea_level: f32,
     pub waves: Vec<GerstnerWave>,
+    /// Wake and ripple heightfield added on top of the wave sum.
+    pub ripples: RippleField,
 }
 
 impl Default for OceanSolver {
     fn default() -> Self {
-        let waves = vec![
-            GerstnerWave::new(60.0, 0.6, 4.0, Vec2::new(1.0, 0.2), 0.4),
-            GerstnerWave::new(35.0, 0.35, 2.5, Vec2::new(0.7, 0.7), 0.6),
-            GerstnerWave::new(15.0, 0.2, 3.5, Vec2::new(0.2, 1.0), 0.8),
-        ];
-        Self { time: 0.0, waves }
+        let base_waves = OceanConfig::default().gerstner_waves();
+        Self {
+            time: 0.0,
+            waves: base_waves.clone(),
+            base_waves,
+            sea_level: MEAN_SEA_LEVEL,
+            ripples: RippleField::default(),
+        }
     }
 }
 
 impl OceanSolver {
+    /// Evaluates the Gerstner sum for the undisplaced (rest) point `rest_xz`, plus the
+    /// ripple layer. Returns the displaced surface point, its normal and water velocity.
+    ///
+    /// Steepness is shared across waves (GPU Gems 1, ch. 1): a steepness of 1.0 on every
+    /// wave is the sharpest crest before the surface loops over itself.
+    pub fn sample(&self, rest_xz: Vec2) -> OceanSample {
+        self.sample_weighted(rest_xz, |_| 1.0, 1.0)
+    }
+
+    /// Render-side sample: waves and ripples too short for the clipmap level at
+    /// `view_distance` fade out. Matches `sample` near the camera.
+    pub fn sample_lod(&self, rest_xz: Vec2, view_distance: f32) -> OceanSample {
+        let fade = |end: f32| {
+            let t = ((view_distance - end * 0.5) / (end * 0.5)).clamp(0.0, 1.0);
+            1.0 - t * t * (3.0 - 2.0 * t)
+        };
+        self.sample_weighted(rest_xz, |wave| fade(lod_fade_end(wave)), fade(ripple_fade_end()))
+    }
+
+    fn sample_weighted(
+        &self,
+        rest_xz: Vec2,
+        weight: impl Fn(&GerstnerWave) -> f32,
+        ripple_weight: f32,
+    ) -> OceanSample {
+        let count = self.waves.len().max(1) as f32;
+        let mut offset = Vec3::ZERO;
+        let mut normal = Vec3::Y;
+        let mut velocity = Vec3::ZERO;
+
+        for wave in &self.waves {
+            let w = weight(wave);
+            if w <= 0.0 {
+                continue;
+            }
+            let k = wave.frequency();
+            let omega = wave.phase_constant();
+            let theta = k * wave.direction.dot(rest_xz) - omega * self.time;
+            let (sin, cos) = theta.sin_cos();
+            let dir = wave.direction;
+            // Horizontal amplitude Q·A with Q = steepness / (k·A·count).
+            let horizontal = w * wave.steepness / (k * count);
+            let amplitude = w * wave.amplitude;
+            let ka = k * amplitude;
+
+            offset.x += dir.x * horizontal * cos;
+            offset.z += dir.y * horizontal * cos;
+            offset.y += amplitude * sin;
+
+            normal.x -= dir.x * ka * cos;
+            normal.z -= dir.y * ka * cos;
+            normal.y -= w * wave.steepness / count * sin;
+
+            velocity.x += dir.x * horizontal * omega * sin;
+            velocity.z += dir.y * horizontal * omega * sin;
+            velocity.y -= amplitude * omega * cos;
+        }
+
+        let mut position = Vec3::new(rest_xz.x, self.sea_level, rest_xz.y) + offset;
+        let mut foam = 0.0;
+        if ripple_weight > 0.0 {
+            let ripple = self.ripples.sample(Vec2::new(position.x, position.z));
+            position.y += ripple.height * ripple_weight;
+            normal.x -= ripple.slope.x * ripple_weight * normal.y;
+            normal.z -= ripple.slope.y * ripple_weight * normal.y;
+            velocity.y += ripple.velocity * ripple_weight;
+            foam = ripple.foam * ripple_weight;
+        }
+
+        OceanSample {
+            position,
+            normal: normal.normalize_or(Vec3::Y),
+            velocity,
+            foam,
+        }
+    }
+
+    /// Surface sample directly above or below a world point. Inverts the horizontal
+    /// displacement with a few fixed-point iterations so `position.xz` matches `pos.xz`.
+    pub fn surface_at(&self, pos: Vec3) -> OceanSample {
+        let target = Vec2::new(pos.x, pos.z);
+        let mut rest = target;
+        let mut sample = self.sample(rest);
+        for _ in 0..SURFACE_INVERSE_ITERATIONS {
+            rest += target - Vec2::new(sample.position.x, sample.position.z);
+            sample = self.sample(rest);
+        }
+        sample
+    }
+
     /// Returns water surface height (Y) at world position.
     /// Bevy: Y is up, horizontal plane is XZ.
     pub fn wave_height_at(&self, pos: Vec3) -> f32 {
-        let wave_offset = self.waves.iter().fold(0.0, |acc, wave| {
-            let freq = wave.frequency();
-            let phase = wave.phase_constant() * self.time;
-            let projected = pos.x * wave.direction.x + pos.z * wave.direction.y;
-            acc + (wave.amplitude * wave.steepness) * (freq * projected + phase).sin()
-        });
-        SEA_LEVEL + wave_offset
+        self.surface_at(pos).position.y
     }
 }
 
@@ -81,16 +200,94 @@ impl Plugin for OceanPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(OceanSolver::default())
             .add_systems(Startup, spawn_water)
-            .add_systems(Update, (update_ocean_time, update_water_mesh));
+            .add_systems(
+                FixedPreUpdate,
+                update_ocean_time.run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(Update, update_water_mesh);
+    }
+}
+
+/// Clipmap levels. Level L has cell size `WATER_LOD_BASE_CELL * 2^L`; 8 levels reach ~8 km.
+const WATER_LOD_LEVELS: usize = 8;
+
+/// Cells across one clipmap level. Must be divisible by 4 (the hole is half the level).
+const WATER_LOD_CELLS: usize = 64;
+
+/// Cell size (m) of the finest level, centred on the camera.
+const WATER_LOD_BASE_CELL: f32 = 1.0;
+
+/// View distance (m) at which a wave has fully faded from the rendered surface.
+/// A level's outer edge is `CELLS / 2` cells out; a wave needs ~4 cells per wavelength.
+fn lod_fade_end(wave: &GerstnerWave) -> f32 {
+    wave.wavelength * WATER_LOD_CELLS as f32 / 8.0
+}
+
+/// Same as `lod_fade_end` for the ripple layer, whose shortest features span ~4 cells.
+fn ripple_fade_end() -> f32 {
+    4.0 * RIPPLE_CELL * WATER_LOD_CELLS as f32 / 8.0
+}
+
+/// One clipmap level. Vertex `i` rests at `origin + rest[i]`; grid is (CELLS+1)² row-major in X.
+#[derive(Component)]
+struct WaterRing {
+    level: usize,
+    cell_size: f32,
+    rest: Vec<Vec2>,
+    /// Snapped world XZ of the ring centre at the last rewrite.
+    origin: Vec2,
+    /// Origin of the finer level filling the hole at the last index rebuild.
+    hole: Option<Vec2>,
+    /// Whether any wave reached this ring at the last rewrite.
+    animated: bool,
+}
+
+impl WaterRing {
+    /// Distance (m) from the camera to the nearest vertex this ring draws.
+    fn inner_distance(&self) -> f32 {
+        if self.level == 0 {
+            0.0
+        } else {
+            (WATER_LOD_CELLS / 4) as f32 * self.cell_size - self.cell_size
+        }
     }
 }
 
-/// Grid size for wave mesh. Bevy Plane3d.subdivisions(n) gives (n+2)×(n+2) vertices.
-/// subdivisions(64) → 66×66 = 4356 vertices.
-const WAVE_GRID: usize = 66;
+/// Ring centre snapped to twice the level's cell size, so the finer level's edges land
+/// on this level's grid lines.
+fn ring_origin(level: usize, cam_xz: Vec2) -> Vec2 {
+    let snap = 2.0 * WATER_LOD_BASE_CELL * (1u32 << level) as f32;
+    (cam_xz / snap).round() * snap
+}
 
-/// Tiling factor for water normal map (how many times it repeats across the ocean).
-const WATER_NORMAL_TILES: f32 = 24.0;
+/// Triangle list for a level, skipping quads covered by the finer level at `hole`.
+fn ring_indices(origin: Vec2, cell_size: f32, hole: Option<Vec2>) -> Vec<u32> {
+    let n = WATER_LOD_CELLS;
+    let row = (n + 1) as u32;
+    let hole_half = (n / 4) as f32 * cell_size;
+    let mut indices = Vec::with_capacity(n * n * 6);
+    for z in 0..n {
+        for x in 0..n {
+            let center = origin
+                + (Vec2::new(x as f32 + 0.5, z as f32 + 0.5) - (n / 2) as f32) * cell_size;
+            if let Some(h) = hole {
+                if (center - h).abs().max_element() < hole_half {
+                    continue;
+                }
+            }
+            let quad = z as u32 * row + x as u32;
+            indices.extend([
+                quad + row + 1,
+                quad + 1,
+                quad + row,
+                quad,
+                quad + row,
+                quad + 1,
+            ]);
+        }
+    }
+    indices
+}
 
 /// Creates a procedural water normal map with fine ripples. Tangent-space; 0-255 RGBA.
 fn create_water_normal_map(size: u32) -> Image {
@@ -128,47 +325,28 @@ fn create_water_normal_map(size: u32) -> Image {
         TextureFormat::Rgba8Unorm,
         RenderAssetUsages::default(),
     );
-    image.sampler = ImageSampler::Descriptor(ImageSamplerDescriptor::default());
+    // World-space UVs run far past 1.0: the ripples must repeat, not clamp.
+    image.sampler = ImageSampler::Descriptor(ImageSamplerDescriptor {
+        address_mode_u: ImageAddressMode::Repeat,
+        address_mode_v: ImageAddressMode::Repeat,
+        ..default()
+    });
     image
 }
 
 fn spawn_water(
     mut commands: Commands,
+    config: Res<ActiveOceanConfig>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     mut images: ResMut<Assets<Image>>,
 ) {
-    // Use subdivided plane for wave deformation (64x64 for large map).
-    let map_size = crate::world::MAP_SIZE;
-    let mut plane_mesh: Mesh = Plane3d::default()
-        .mesh()
-        .size(map_size, map_size)
-        .subdivisions(64)
-        .build();
-    plane_mesh.asset_usage = RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD;
-
-    // Scale UVs so the normal map tiles across the ocean for fine ripples
-    if let Some(VertexAttributeValues::Float32x2(uvs)) =
-        plane_mesh.attribute_mut(Mesh::ATTRIBUTE_UV_0)
-    {
-        for uv in uvs.iter_mut() {
-            uv[0] *= WATER_NORMAL_TILES;
-            uv[1] *= WATER_NORMAL_TILES;
-        }
-    }
-
-    let n = WAVE_GRID * WAVE_GRID;
-    let water_tint = [0.2, 0.4, 0.6, 0.98];
-    plane_mesh.insert_attribute(
-        Mesh::ATTRIBUTE_COLOR,
-        (0..n).map(|_| water_tint).collect::<Vec<[f32; 4]>>(),
-    );
-    let handle = meshes.add(plane_mesh);
-
+    let config = &config.0;
     let water_normal = images.add(create_water_normal_map(128));
+    let [r, g, b, a] = config.base_color;
     let water_material = materials.add(StandardMaterial {
-        base_color: Color::srgba(0.08, 0.25, 0.45, 0.95),
-        perceptual_roughness: 0.02,
+        base_color: Color::srgba(r, g, b, a),
+        perceptual_roughness: config.roughness,
         metallic: 0.0,
         alpha_mode: AlphaMode::Blend,
         reflectance: 0.5,
@@ -178,73 +356,183 @@ fn spawn_water(
         ..default()
     });
 
-    commands.insert_resource(WaterMeshHandle(handle.clone()));
-    commands.spawn((
-        Mesh3d(handle),
-        MeshMaterial3d(water_material),
-        Transform::default(),
-    ));
+    commands.insert_resource(WaterMaterial(water_material.clone()));
+
+    let n = WATER_LOD_CELLS;
+    let water_tint = config.water_tint;
+    for level in 0..WATER_LOD_LEVELS {
+        let cell_size = WATER_LOD_BASE_CELL * (1u32 << level) as f32;
+        let rest: Vec<Vec2> = (0..(n + 1) * (n + 1))
+            .map(|idx| {
+                let (i, j) = (idx % (n + 1), idx / (n + 1));
+                Vec2::new(i as f32 - (n / 2) as f32, j as f32 - (n / 2) as f32) * cell_size
+            })
+            .collect();
+        let hole = (level > 0).then_some(Vec2::ZERO);
+
+        let mut mesh = Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
+        );
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            rest.iter().map(|r| [r.x, 0.0, r.y]).collect::<Vec<[f32; 3]>>(),
+        );
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 1.0, 0.0]; rest.len()]);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0, 0.0]; rest.len()]);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, vec![water_tint; rest.len()]);
+        mesh.insert_indices(Indices::U32(ring_indices(Vec2::ZERO, cell_size, hole)));
+
+        commands.spawn((
+            Mesh3d(meshes.add(mesh)),
+            MeshMaterial3d(water_material.clone()),
+            Transform::default(),
+            // Vertices move every frame; the spawn-time AABB is meaningless.
+            NoFrustumCulling,
+            WaterRing {
+                level,
+                cell_size,
+                rest,
+                // Force a full rewrite on the first update.
+                origin: Vec2::splat(f32::NAN),
+                hole,
+                animated: true,
+            },
+        ));
+    }
 }
 
-fn update_ocean_time(time: Res<Time>, mut ocean: ResMut<OceanSolver>) {
-    ocean.time = time.elapsed_secs();
+/// Wave phase follows the simulation clock, so it holds still outside Playing.
+fn update_ocean_time(clock: Res<SimClock>, mut ocean: ResMut<OceanSolver>) {
+    ocean.time = clock.elapsed_secs();
 }
 
+/// Rewrites only the rings that changed: ones the camera snapped across, and ones near
+/// enough for some wave or the wake to reach them. Far rings stay flat and just follow the tide.
 fn update_water_mesh(
     ocean: Res<OceanSolver>,
-    handle: Res<WaterMeshHandle>,
+    config: Res<ActiveOceanConfig>,
+    camera_query: Query<&GlobalTransform, With<PlayerCamera>>,
     mut meshes: ResMut<Assets<Mesh>>,
+    mut ring_query: Query<(&mut WaterRing, &Mesh3d, &mut Transform)>,
 ) {
-    let Some(mesh) = meshes.get_mut(&handle.0) else { return };
-    let n = WAVE_GRID;
-    let map_size = crate::world::MAP_SIZE;
-    let cell_size = map_size / 65.0; // 65 cells between 66 vertices (subdivisions=64)
-    let water_tint = [0.2, 0.4, 0.6, 0.98];
-    let foam_tint = [1.0, 1.0, 1.0, 1.0];
-    let steepness_lo = 0.012;
-    let steepness_hi = 0.06;
-
-    let mut heights = vec![0.0f32; n * n];
-    {
-        let Some(VertexAttributeValues::Float32x3(positions)) =
+    let Some(cam_global) = camera_query.iter().next() else { return };
+    let cam = cam_global.translation();
+    let cam_xz = Vec2::new(cam.x, cam.z);
+    let n = WATER_LOD_CELLS;
+    let row = n + 1;
+    let restyled = config.is_changed();
+    let config = &config.0;
+    let normal_tile = crate::world::MAP_SIZE / config.normal_tiles.max(0.01);
+    let water_tint = config.water_tint;
+    let foam_tint = config.foam_tint;
+    // Thresholds on analytic slope |n.xz| / n.y.
+    let (steepness_lo, steepness_hi) = config.foam_steepness;
+
+    for (mut ring, mesh3d, mut transform) in ring_query.iter_mut() {
+        let origin = ring_origin(ring.level, cam_xz);
+        let hole = (ring.level > 0).then(|| ring_origin(ring.level - 1, cam_xz));
+        let anchor = Vec3::new(origin.x, ocean.sea_level, origin.y);
+        transform.translation = anchor;
+
+        let moved = origin != ring.origin;
+        let animated = ocean
+            .waves
+            .iter()
+            .any(|wave| lod_fade_end(wave) > ring.inner_distance())
+            || ripple_fade_end() > ring.inner_distance();
+        if !moved && !animated && !ring.animated && hole == ring.hole && !restyled {
+            continue;
+        }
+        let Some(mesh) = meshes.get_mut(&mesh3d.0) else { continue };
+
+        if hole != ring.hole {
+            mesh.insert_indices(Indices::U32(ring_indices(origin, ring.cell_size, hole)));
+        }
+
+        // Vertices under the finer level are never drawn; skip the wave sum for them.
+        let hole_half = (n / 4) as f32 * ring.cell_size - 0.5 * ring.cell_size;
+        let samples: Vec<Option<OceanSample>> = ring
+            .rest
+            .iter()
+            .map(|rest| {
+                let world = origin + *rest;
+                if let Some(h) = hole {
+                    if (world - h).abs().max_element() < hole_half {
+                        return None;
+                    }
+                }
+                if !animated {
+                    return Some(OceanSample {
+                        position: Vec3::new(world.x, ocean.sea_level, world.y),
+                        normal: Vec3::Y,
+                        velocity: Vec3::ZERO,
+                        foam: 0.0,
+                    });
+                }
+                Some(ocean.sample_lod(world, world.distance(cam_xz)))
+            })
+            .collect();
+
+        if let Some(VertexAttributeValues::Float32x3(positions)) =
             mesh.attribute_mut(Mesh::ATTRIBUTE_POSITION)
-        else {
-            return;
-        };
-        for i in 0..n {
-            for j in 0..n {
-                let idx = i + j * n;
-                let world_pos = Vec3::new(positions[idx][0], 0.0, positions[idx][2]);
-                let h = ocean.wave_height_at(world_pos);
-                positions[idx][1] = h;
-                heights[idx] = h;
+        {
+            for (pos, sample) in positions.iter_mut().zip(&samples) {
+                if let Some(sample) = sample {
+                    *pos = (sample.position - anchor).to_array();
+                }
+            }
+            // Seams: odd vertices on the outer edge sit mid-way along a coarser edge of the
+            // next level. Pin them to the midpoint so the two levels meet without cracks.
+            for k in (1..n).step_by(2) {
+                for (idx, step) in [(k, 1), (n * row + k, 1), (k * row, row), (k * row + n, row)] {
+                    let a = Vec3::from_array(positions[idx - step]);
+                    let b = Vec3::from_array(positions[idx + step]);
+                    positions[idx] = ((a + b) * 0.5).to_array();
+                }
             }
         }
-    }
 
-    {
-        let Some(VertexAttributeValues::Float32x4(colors)) =
+        if let Some(VertexAttributeValues::Float32x3(normals)) =
+            mesh.attribute_mut(Mesh::ATTRIBUTE_NORMAL)
+        {
+            for (normal, sample) in normals.iter_mut().zip(&samples) {
+                if let Some(sample) = sample {
+                    *normal = sample.normal.to_array();
+                }
+            }
+        }
+
+        if moved || restyled {
+            if let Some(VertexAttributeValues::Float32x2(uvs)) =
+                mesh.attribute_mut(Mesh::ATTRIBUTE_UV_0)
+            {
+                // World-space UVs keep the ripple texture continuous across levels.
+                for (uv, rest) in uvs.iter_mut().zip(&ring.rest) {
+                    let world = origin + *rest;
+                    *uv = (world / normal_tile).to_array();
+                }
+            }
+        }
+
+        if let Some(VertexAttributeValues::Float32x4(colors)) =
             mesh.attribute_mut(Mesh::ATTRIBUTE_COLOR)
-        else {
-            return;
-        };
-        for i in 0..n {
-            for j in 0..n {
-                let idx = i + j * n;
-                let h = heights[idx];
-                let h_l = if i > 0 { heights[idx - 1] } else { h };
-                let h_r = if i + 1 < n { heights[idx + 1] } else { h };
-                let h_d = if j > 0 { heights[idx - n] } else { h };
-                let h_u = if j + 1 < n { heights[idx + n] } else { h };
-                let grad_x = (h_r - h_l) / (2.0 * cell_size);
-                let grad_z = (h_u - h_d) / (2.0 * cell_size);
-                let steepness = (grad_x * grad_x + grad_z * grad_z).sqrt();
-                let foam = ((steepness - steepness_lo) / (steepness_hi - steepness_lo))
+        {
+            for (color, sample) in colors.iter_mut().zip(&samples) {
+                let Some(sample) = sample else { continue };
+                let nrm = sample.normal;
+                let steepness = Vec2::new(nrm.x, nrm.z).length() / nrm.y.max(0.01);
+                let crest = ((steepness - steepness_lo) / (steepness_hi - steepness_lo).max(0.001))
                     .clamp(0.0, 1.0);
+                let foam = crest.max(sample.foam);
                 for c in 0..4 {
-                    colors[idx][c] = water_tint[c] * (1.0 - foam) + foam_tint[c] * foam;
+                    color[c] = water_tint[c] * (1.0 - foam) + foam_tint[c] * foam;
                 }
             }
         }
+
+        ring.origin = origin;
+        ring.hole = hole;
+        ring.animated = animated;
     }
 }
@@ -0,0 +1,210 @@
+//! Ocean presets – `OceanConfig` assets loaded from assets/ocean/*.ocean.ron.
+//!
+//! The active preset feeds the `OceanSolver` base swell and the water material.
+//! Files are watched: saving a preset while the game runs applies it live.
+
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext};
+use bevy::prelude::*;
+use bevy::reflect::TypePath;
+use serde::Deserialize;
+
+use crate::ocean::{GerstnerWave, OceanSolver, WaterMaterial};
+
+/// One Gerstner layer as authored in RON.
+#[derive(Deserialize, Clone, Copy)]
+pub struct WaveConfig {
+    pub wavelength: f32,
+    pub amplitude: f32,
+    pub speed: f32,
+    pub direction: [f32; 2],
+    pub steepness: f32,
+}
+
+/// Sea look and calm-sea swell. Weather scales the waves; everything else is used as-is.
+#[derive(Asset, TypePath, Deserialize, Clone)]
+pub struct OceanConfig {
+    pub waves: Vec<WaveConfig>,
+    /// How many times the ripple normal map repeats across the map.
+    pub normal_tiles: f32,
+    /// Vertex colour of calm water (linear RGBA).
+    pub water_tint: [f32; 4],
+    /// Vertex colour of full foam (linear RGBA).
+    pub foam_tint: [f32; 4],
+    /// Surface slope (|n.xz| / n.y) where foam starts and where it is full.
+    pub foam_steepness: (f32, f32),
+    /// Water material base colour (sRGBA).
+    pub base_color: [f32; 4],
+    pub roughness: f32,
+}
+
+impl Default for OceanConfig {
+    fn default() -> Self {
+        Self {
+            waves: vec![
+                WaveConfig { wavelength: 60.0, amplitude: 0.6, speed: 4.0, direction: [1.0, 0.2], steepness: 0.4 },
+                WaveConfig { wavelength: 35.0, amplitude: 0.35, speed: 2.5, direction: [0.7, 0.7], steepness: 0.6 },
+                WaveConfig { wavelength: 15.0, amplitude: 0.2, speed: 3.5, direction: [0.2, 1.0], steepness: 0.8 },
+            ],
+            normal_tiles: 24.0,
+            water_tint: [0.2, 0.4, 0.6, 0.98],
+            foam_tint: [1.0, 1.0, 1.0, 1.0],
+            foam_steepness: (0.12, 0.2),
+            base_color: [0.08, 0.25, 0.45, 0.95],
+            roughness: 0.02,
+        }
+    }
+}
+
+impl OceanConfig {
+    pub fn gerstner_waves(&self) -> Vec<GerstnerWave> {
+        self.waves
+            .iter()
+            .map(|w| {
+                GerstnerWave::new(
+                    w.wavelength,
+                    w.amplitude,
+                    w.speed,
+                    Vec2::from_array(w.direction),
+                    w.steepness,
+                )
+            })
+            .collect()
+    }
+}
+
+/// Name of the active preset: loads assets/ocean/<name>.ocean.ron. Change it to switch presets.
+#[derive(Resource)]
+pub struct OceanPreset(pub String);
+
+impl Default for OceanPreset {
+    fn default() -> Self {
+        Self("default".into())
+    }
+}
+
+/// Copy of the active preset. Built-in defaults until the file has loaded.
+#[derive(Resource, Default)]
+pub struct ActiveOceanConfig(pub OceanConfig);
+
+#[derive(Resource, Default)]
+struct OceanConfigHandle(Handle<OceanConfig>);
+
+#[derive(Debug)]
+pub enum OceanConfigLoadError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+}
+
+impl std::fmt::Display for OceanConfigLoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OceanConfigLoadError::Io(e) => write!(f, "could not read ocean config: {}", e),
+            OceanConfigLoadError::Ron(e) => write!(f, "could not parse ocean config: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for OceanConfigLoadError {}
+
+impl From<std::io::Error> for OceanConfigLoadError {
+    fn from(e: std::io::Error) -> Self {
+        OceanConfigLoadError::Io(e)
+    }
+}
+
+impl From<ron::error::SpannedError> for OceanConfigLoadError {
+    fn from(e: ron::error::SpannedError) -> Self {
+        OceanConfigLoadError::Ron(e)
+    }
+}
+
+#[derive(Default)]
+struct OceanConfigLoader;
+
+impl AssetLoader for OceanConfigLoader {
+    type Asset = OceanConfig;
+    type Settings = ();
+    type Error = OceanConfigLoadError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<OceanConfig, OceanConfigLoadError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes::<OceanConfig>(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ocean.ron"]
+    }
+}
+
+pub struct OceanConfigPlugin;
+
+impl Plugin for OceanConfigPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<OceanConfig>()
+            .init_asset_loader::<OceanConfigLoader>()
+            .init_resource::<OceanPreset>()
+            .init_resource::<ActiveOceanConfig>()
+            .init_resource::<OceanConfigHandle>()
+            .add_systems(
+                Update,
+                (
+                    load_ocean_preset.run_if(resource_changed::<OceanPreset>),
+                    apply_ocean_config,
+                )
+                    .chain(),
+            );
+    }
+}
+
+fn load_ocean_preset(
+    preset: Res<OceanPreset>,
+    asset_server: Res<AssetServer>,
+    mut handle: ResMut<OceanConfigHandle>,
+) {
+    handle.0 = asset_server.load(format!("ocean/{}.ocean.ron", preset.0));
+}
+
+/// Copies the active preset into the solver and water material when it loads or changes on disk.
+fn apply_ocean_config(
+    mut events: MessageReader<AssetEvent<OceanConfig>>,
+    handle: Res<OceanConfigHandle>,
+    configs: Res<Assets<OceanConfig>>,
+    water_material: Option<Res<WaterMaterial>>,
+    mut active: ResMut<ActiveOceanConfig>,
+    mut ocean: ResMut<OceanSolver>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let mut changed = false;
+    for event in events.read() {
+        match event {
+            AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id }
+                if *id == handle.0.id() =>
+            {
+                changed = true;
+            }
+            _ => {}
+        }
+    }
+    if !changed {
+        return;
+    }
+    let Some(config) = configs.get(&handle.0) else { return };
+
+    active.0 = config.clone();
+    ocean.base_waves = config.gerstner_waves();
+    if let Some(water_material) = water_material {
+        if let Some(mat) = materials.get_mut(&water_material.0) {
+            let [r, g, b, a] = config.base_color;
+            mat.base_color = Color::srgba(r, g, b, a);
+            mat.perceptual_roughness = config.roughness;
+        }
+    }
+    bevy::log::info!("Applied ocean preset from {:?}", handle.0.path());
+}
@@ -8,17 +8,20 @@ use bevy::post_process::bloom::Bloom;
 use bevy::render::view::{ColorGrading, Hdr};
 use bevy::text::TextLayout;
 
-use crate::artifacts::{Artifact, AttachedArtifact, Inventory};
+use crate::artifacts::{is_heavy, Artifact, AttachedArtifact, Inventory};
 use crate::audio::ArtifactPickupEvent;
 use crate::character::MarineCharacter;
+use crate::cradle::Cradle;
 use crate::game_state::GameState;
+use crate::manipulator::Manipulator;
 use crate::interaction::{
     nearest_interactable_in_range, nearest_interactable_out_of_range, Interactable, InteractKind,
 };
 use crate::diving_bell::Submersible;
-use crate::ocean::SEA_LEVEL;
 use crate::ship::Ship;
 use crate::settings::InputBindings;
+use crate::tide::Tide;
+use crate::weather::Weather;
 
 /// Distance (m) at which E can enter ship or sub.
 pub const VEHICLE_ENTER_RANGE: f32 = 6.0;
@@ -33,11 +36,25 @@ impl PlayerMode {
     pub fn in_vehicle(&self) -> bool {
         self.in_boat || self.in_submersible
     }
+
+    /// At the helm, or on foot within reach of the ship: close enough for deck work.
+    pub fn can_work_ship(&self, character: Option<&Transform>, ship: &Transform) -> bool {
+        let alongside = character
+            .is_some_and(|t| t.translation.distance(ship.translation) <= VEHICLE_ENTER_RANGE * 2.0);
+        self.in_boat || (!self.in_vehicle() && alongside)
+    }
 }
 
 #[derive(Component)]
 pub struct PlayerCamera;
 
+/// Hands the camera back to the character at eye height, for a player thrown out of a
+/// vehicle rather than leaving it with E.
+pub fn put_camera_on_character(commands: &mut Commands, cam_id: Entity, char_id: Entity) {
+    commands.entity(char_id).add_child(cam_id);
+    commands.entity(cam_id).insert(Transform::from_xyz(0.0, 0.9, 0.0));
+}
+
 #[derive(Component)]
 struct InteractPrompt;
 
@@ -67,11 +84,12 @@ impl Plugin for PlayerPlugin {
 const DEPTH_COLOR_TRANSITION: f32 = 25.0;
 
 fn update_depth_color_grading(
+    tide: Res<Tide>,
     mut camera_query: Query<(&mut ColorGrading, &GlobalTransform), With<PlayerCamera>>,
 ) {
     for (mut grading, global) in camera_query.iter_mut() {
         let y = global.translation().y;
-        let depth = SEA_LEVEL - y;
+        let depth = tide.level - y;
         let depth_factor = if depth > 0.0 {
             let t = (depth / DEPTH_COLOR_TRANSITION).min(1.0);
             let smooth = t * t * (3.0 - 2.0 * t);
@@ -85,27 +103,33 @@ fn update_depth_color_grading(
 }
 
 fn update_depth_fog(
+    weather: Res<Weather>,
+    tide: Res<Tide>,
     mut camera_query: Query<(&mut bevy::pbr::DistanceFog, &GlobalTransform), With<PlayerCamera>>,
 ) {
     use bevy::pbr::FogFalloff;
+    let surface_fog = weather.params.fog_color.to_srgba();
+    let surface_density = weather.params.fog_density;
     for (mut fog, global) in camera_query.iter_mut() {
         let y = global.translation().y;
-        let depth = SEA_LEVEL - y;
+        let depth = tide.level - y;
         if depth > 0.0 {
             let d = depth.min(80.0);
             let density = 0.006 + 0.012 * (d / 80.0).powf(0.7);
             let t = (depth / DEPTH_COLOR_TRANSITION).min(1.0);
             let smooth = t * t * (3.0 - 2.0 * t);
             fog.color = Color::srgba(
-                0.2 * smooth + 0.5 * (1.0 - smooth),
-                0.35 * smooth + 0.6 * (1.0 - smooth),
-                0.6 * smooth + 0.8 * (1.0 - smooth),
-                0.35 * smooth + 0.2 * (1.0 - smooth),
+                0.2 * smooth + surface_fog.red * (1.0 - smooth),
+                0.35 * smooth + surface_fog.green * (1.0 - smooth),
+                0.6 * smooth + surface_fog.blue * (1.0 - smooth),
+                0.35 * smooth + surface_fog.alpha * (1.0 - smooth),
             );
-            fog.falloff = FogFalloff::Exponential { density };
+            fog.falloff = FogFalloff::Exponential {
+                density: density.max(surface_density * (1.0 - smooth)),
+            };
         } else {
-            fog.color = Color::srgba(0.5, 0.6, 0.8, 0.2);
-            fog.falloff = FogFalloff::Exponential { density: 0.008 };
+            fog.color = weather.params.fog_color;
+            fog.falloff = FogFalloff::Exponential { density: surface_density };
         }
     }
 }
@@ -149,6 +173,8 @@ fn update_interact_prompt(
     ship_query: Query<&Transform, With<Ship>>,
     sub_query: Query<&Transform, With<Submersible>>,
     interactable_query: Query<(Entity, &Transform, &Interactable)>,
+    arm_query: Query<&Manipulator>,
+    artifact_query: Query<&Artifact>,
     mut visibility_query: Query<&mut Visibility>,
     mut text_query: Query<&mut Text>,
     children_query: Query<&Children>,
@@ -183,18 +209,21 @@ fn update_interact_prompt(
 
     // In vehicle (boat or sub with no attach): show exit
     if mode.in_vehicle() {
-        // In sub: also check for nearby AttachToWinch
-        if mode.in_submersible {
-            if let Some((_, kind, _)) =
-                nearest_interactable_in_range(pos, interactable_query.iter())
-            {
-                if matches!(kind, InteractKind::AttachToWinch { .. }) {
-                    *vis = Visibility::Visible;
-                    if let Ok(mut text) = text_query.get_mut(text_entity) {
-                        *text = Text::new(kind.prompt());
-                    }
-                    return;
+        // In sub: also check what the claw holds, or a nearby AttachToWinch within its reach
+        if let Some(arm) = arm_query.iter().next().filter(|_| mode.in_submersible) {
+            let held = arm.held.and_then(|art_id| artifact_query.get(art_id).ok());
+            let hook = held
+                .map(|artifact| format!("Press E to attach {} to winch", artifact.item_id))
+                .or_else(|| {
+                    nearest_interactable_in_range(arm.tip, interactable_query.iter().filter(is_winch_hook))
+                        .map(|(_, kind, _)| kind.prompt())
+                });
+            if let Some(hook) = hook {
+                *vis = Visibility::Visible;
+                if let Ok(mut text) = text_query.get_mut(text_entity) {
+                    *text = Text::new(hook);
                 }
+                return;
             }
         }
         *vis = Visibility::Visible;
@@ -230,6 +259,14 @@ fn update_interact_prompt(
     }
 }
 
+/// Heavy artifact waiting for the winch hook.
+fn is_winch_hook((_, _, interactable): &(Entity, &Transform, &Interactable)) -> bool {
+    matches!(interactable.kind, InteractKind::AttachToWinch { .. })
+}
+
+/// Body-space spot (m) on the ship's afterdeck where the helmsman steps off the helm.
+const SHIP_HELM: Vec3 = Vec3::new(0.0, 2.7, 6.0);
+
 /// Local offset of attached artifact below sub (hanging from winch).
 const ATTACHED_ARTIFACT_OFFSET: Vec3 = Vec3::new(0.0, -4.0, 0.0);
 
@@ -240,10 +277,12 @@ fn toggle_boat_enter(
     mut attached: ResMut<AttachedArtifact>,
     mut inventory: ResMut<Inventory>,
     mut pickup_events: MessageWriter<ArtifactPickupEvent>,
+    mut cradle: ResMut<Cradle>,
     mut commands: Commands,
     camera_query: Query<Entity, With<PlayerCamera>>,
     character_query: Query<(Entity, &Transform), With<MarineCharacter>>,
-    sub_query: Query<(Entity, &Transform), With<Submersible>>,
+    ship_query: Query<&Transform, With<Ship>>,
+    mut sub_query: Query<(Entity, &mut Manipulator), With<Submersible>>,
     interactable_query: Query<(Entity, &Transform, &Interactable)>,
     artifact_query: Query<&Artifact>,
     global_transform_query: Query<&GlobalTransform>,
@@ -265,16 +304,26 @@ fn toggle_boat_enter(
                     .get(art_id)
                     .map(|a| a.item_id.clone())
                     .unwrap_or_else(|_| "Heavy Artifact".into());
+                // Small artifacts hooked from the claw go back to being hand-carried
+                let (collider, kind) = if is_heavy(&item_id) {
+                    (
+                        bevy_rapier3d::prelude::Collider::cuboid(0.5, 0.5, 0.6),
+                        InteractKind::AttachToWinch { item_id: item_id.clone() },
+                    )
+                } else {
+                    (
+                        bevy_rapier3d::prelude::Collider::cuboid(0.25, 0.25, 0.4),
+                        InteractKind::Pickup { item_id: item_id.clone() },
+                    )
+                };
                 commands.entity(art_id).remove_parent_in_place();
                 commands.entity(art_id).insert((
                     Transform::from_translation(global.translation())
                         .with_rotation(global.rotation()),
                     bevy_rapier3d::prelude::RigidBody::Dynamic,
-                    bevy_rapier3d::prelude::Collider::cuboid(0.5, 0.5, 0.6),
+                    collider,
                     Interactable {
-                        kind: InteractKind::AttachToWinch {
-                            item_id: item_id.clone(),
-                        },
+                        kind,
                         range: VEHICLE_ENTER_RANGE,
                     },
                 ));
@@ -282,27 +331,34 @@ fn toggle_boat_enter(
             attached.0 = None;
             return;
         }
-        // Check for nearby AttachToWinch
-        if let Ok((sub_id, sub_tf)) = sub_query.single() {
-            let sub_pos = sub_tf.translation;
-            if let Some((target_id, kind, _)) =
-                nearest_interactable_in_range(sub_pos, interactable_query.iter())
-            {
-                if let InteractKind::AttachToWinch { .. } = kind {
-                    commands.entity(target_id).remove::<bevy_rapier3d::prelude::RigidBody>();
-                    commands.entity(target_id).remove::<bevy_rapier3d::prelude::Collider>();
-                    commands.entity(target_id).remove::<Interactable>();
-                    commands.entity(target_id).insert(Transform::from_translation(ATTACHED_ARTIFACT_OFFSET));
-                    commands.entity(sub_id).add_child(target_id);
-                    attached.0 = Some(target_id);
-                    return;
-                }
+        // Hook onto the winch whatever the claw holds, else a nearby AttachToWinch within its reach
+        if let Ok((sub_id, mut arm)) = sub_query.single_mut() {
+            let target = arm.release(&mut commands).or_else(|| {
+                nearest_interactable_in_range(arm.tip, interactable_query.iter().filter(is_winch_hook))
+                    .map(|(target_id, _, _)| target_id)
+            });
+            if let Some(target_id) = target {
+                commands.entity(target_id).remove::<bevy_rapier3d::prelude::RigidBody>();
+                commands.entity(target_id).remove::<bevy_rapier3d::prelude::Collider>();
+                commands.entity(target_id).remove::<Interactable>();
+                commands.entity(target_id).insert(Transform::from_translation(ATTACHED_ARTIFACT_OFFSET));
+                commands.entity(sub_id).add_child(target_id);
+                attached.0 = Some(target_id);
+                return;
             }
         }
     }
 
-    // Exit vehicle -> character
+    // Exit vehicle -> character. Leaving the helm puts you on deck, riding the ship.
     if mode.in_vehicle() {
+        if mode.in_boat {
+            if let Ok(ship_tf) = ship_query.single() {
+                let deck = ship_tf.translation + ship_tf.rotation * SHIP_HELM;
+                commands
+                    .entity(char_id)
+                    .insert(Transform::from_translation(deck).with_rotation(char_tf.rotation));
+            }
+        }
         mode.in_boat = false;
         mode.in_submersible = false;
         commands.entity(cam_id).despawn();
@@ -369,6 +425,11 @@ fn toggle_boat_enter(
                 let cam_id = commands.spawn((camera_components, Transform::from_xyz(0.0, 0.9, 0.0))).id();
                 commands.entity(char_id).add_children(&[cam_id]);
             }
+            InteractKind::LaunchSub | InteractKind::RecoverSub => {
+                cradle.request();
+                let cam_id = commands.spawn((camera_components, Transform::from_xyz(0.0, 0.9, 0.0))).id();
+                commands.entity(char_id).add_children(&[cam_id]);
+            }
             #[allow(unreachable_patterns)]
             _ => {
                 // Unhandled kind: ensure camera stays on character
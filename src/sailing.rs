@@ -0,0 +1,339 @@
+//! Sailing – raise and trim the sails, steer with the rudder (proj.md: "manage sails/wheel").
+//!
+//! The sail is a wing in the apparent wind (weather wind minus boat velocity). The boom
+//! swings to leeward as far as the sheet allows; lift and drag from the angle of attack
+//! give the point-of-sail curve: nothing in irons, hard driving on a reach, pushing on a run.
+//! Side force at the centre of effort heels the boat; the keel resists leeway.
+//! W/S hoist and lower, Q/Z sheet in and ease, A/D rudder.
+
+use std::f32::consts::FRAC_PI_2;
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+use crate::game_state::GameState;
+use crate::hud::{spawn_hud_row, SAIL_SLOT};
+use crate::player::PlayerMode;
+use crate::settings::InputBindings;
+use crate::ship::{ship_movement, Ship, ShipPropulsion, WATER_DENSITY};
+use crate::weather::Weather;
+
+/// Air density (kg/m³).
+const AIR_DENSITY: f32 = 1.225;
+
+/// Apparent wind angle off the bow (rad) inside which the sail can't draw: in irons.
+const NO_GO_ANGLE: f32 = 0.61; // 35°
+
+/// Width (rad) of the band below `NO_GO_ANGLE` where the sail goes from luffing to full.
+const LUFF_BAND: f32 = 0.17;
+
+/// Boom angle off the centreline (rad) with the sheet hauled all the way in.
+const MIN_BOOM_ANGLE: f32 = 0.17;
+
+/// Boom angle (rad) with the sheet eased all the way out; the shrouds stop it here.
+const MAX_BOOM_ANGLE: f32 = 1.48;
+
+/// Hoist fraction per second while W/S is held.
+const HOIST_RATE: f32 = 0.35;
+
+/// Sheet fraction per second while Q/Z is held.
+const SHEET_RATE: f32 = 0.4;
+
+/// Rudder yaw torque per (m/s)² of forward speed at full helm (N·m). At 2 m/s of way this
+/// swings the hull through a tack in well under the time it takes to lose that way.
+const RUDDER_TORQUE: f32 = 300000.0;
+
+/// Keel lift coefficient against leeway.
+const KEEL_LIFT: f32 = 1.0;
+
+/// Mast height above the deck (m), for the rig visuals.
+const MAST_HEIGHT: f32 = 9.0;
+
+/// Sail foot length along the boom (m), for the rig visuals.
+const BOOM_LENGTH: f32 = 5.0;
+
+#[derive(Component)]
+pub struct Sails {
+    /// Sail area fully hoisted (m²).
+    pub area: f32,
+    /// 0 = furled, 1 = fully hoisted.
+    pub hoisted: f32,
+    /// 0 = hauled in tight, 1 = eased all the way out.
+    pub sheet: f32,
+    /// Body-space point the sail force acts at (m).
+    pub centre_of_effort: Vec3,
+    /// Body-space mast foot (m).
+    pub mast_step: Vec3,
+    /// Lateral area of the keel (m²).
+    pub keel_area: f32,
+    /// Body-space centre of lateral resistance of the keel (m).
+    pub keel_centre: Vec3,
+    /// Boom angle off the centreline at the last step (rad, + to starboard).
+    pub boom_angle: f32,
+    /// Apparent wind angle off the bow at the last step (rad, 0..π).
+    pub apparent_wind_angle: f32,
+    /// Apparent wind speed at the last step (m/s).
+    pub apparent_wind_speed: f32,
+}
+
+impl Sails {
+    /// Single gaff sail stepped forward of amidships on the rowboat hull.
+    pub fn rowboat() -> Self {
+        Self {
+            area: 250.0,
+            hoisted: 0.0,
+            sheet: 0.5,
+            centre_of_effort: Vec3::new(0.0, 6.5, -1.0),
+            mast_step: Vec3::new(0.0, 1.75, -3.5),
+            keel_area: 12.0,
+            keel_centre: Vec3::new(0.0, -2.5, 0.0),
+            boom_angle: 0.0,
+            apparent_wind_angle: 0.0,
+            apparent_wind_speed: 0.0,
+        }
+    }
+
+    pub fn in_irons(&self) -> bool {
+        self.hoisted > 0.0 && self.apparent_wind_angle < NO_GO_ANGLE
+    }
+}
+
+#[derive(Component)]
+struct SailPivot;
+
+#[derive(Component)]
+struct SailCloth;
+
+#[derive(Component)]
+struct SailUiText;
+
+#[derive(Resource)]
+struct SailUiRoot(Entity);
+
+pub struct SailingPlugin;
+
+impl Plugin for SailingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_sail_ui)
+            .add_systems(
+                Update,
+                (
+                    sail_input
+                        .run_if(in_state(GameState::Playing))
+                        .run_if(|mode: Res<PlayerMode>| mode.in_boat),
+                    spawn_sail_rig,
+                    update_sail_rig,
+                    update_sail_ui.run_if(in_state(GameState::Playing)),
+                ),
+            )
+            .add_systems(
+                FixedUpdate,
+                sail_forces
+                    .after(ship_movement)
+                    .before(PhysicsSet::SyncBackend)
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+fn sail_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<InputBindings>,
+    time: Res<Time>,
+    mut query: Query<(&Ship, &mut Sails)>,
+) {
+    let dt = time.delta_secs();
+    for (ship, mut sails) in query.iter_mut() {
+        if ship.propulsion != ShipPropulsion::Sail {
+            continue;
+        }
+        if keyboard.pressed(bindings.forward) {
+            sails.hoisted = (sails.hoisted + HOIST_RATE * dt).min(1.0);
+        }
+        if keyboard.pressed(bindings.back) {
+            sails.hoisted = (sails.hoisted - HOIST_RATE * dt).max(0.0);
+        }
+        if keyboard.pressed(bindings.sheet_in) {
+            sails.sheet = (sails.sheet - SHEET_RATE * dt).max(0.0);
+        }
+        if keyboard.pressed(bindings.sheet_out) {
+            sails.sheet = (sails.sheet + SHEET_RATE * dt).min(1.0);
+        }
+    }
+}
+
+/// Sail lift and drag at the centre of effort, keel lift against leeway, rudder with way on.
+/// A hoisted sail draws under engine too.
+fn sail_forces(
+    weather: Res<Weather>,
+    mut query: Query<(&Transform, &Velocity, &Ship, &mut Sails, &mut ExternalForce)>,
+) {
+    let wind = weather.wind();
+    for (transform, velocity, ship, mut sails, mut ext_force) in query.iter_mut() {
+        let forward = transform.forward().as_vec3();
+        let heading = Vec3::new(forward.x, 0.0, forward.z).normalize_or(Vec3::NEG_Z);
+        let starboard = heading.cross(Vec3::Y);
+        let hull_velocity = Vec3::new(velocity.linvel.x, 0.0, velocity.linvel.z);
+
+        let apparent = wind - hull_velocity;
+        let speed = apparent.length();
+        let mut force = Vec3::ZERO;
+        let mut torque = Vec3::ZERO;
+        if speed > 0.01 {
+            let flow = apparent / speed;
+            let from = -flow;
+            let awa = heading.dot(from).clamp(-1.0, 1.0).acos();
+            let wind_side = if starboard.dot(from) >= 0.0 { 1.0 } else { -1.0 };
+
+            // Boom goes to leeward, out to the sheet but never past the wind.
+            let sheet_limit = MIN_BOOM_ANGLE + sails.sheet * (MAX_BOOM_ANGLE - MIN_BOOM_ANGLE);
+            let boom = sheet_limit.min(awa);
+            let alpha = (awa - boom).clamp(0.0, FRAC_PI_2);
+            let t = ((awa - (NO_GO_ANGLE - LUFF_BAND)) / LUFF_BAND).clamp(0.0, 1.0);
+            let drawing = t * t * (3.0 - 2.0 * t);
+            let lift = 1.5 * (2.0 * alpha).sin() * drawing;
+            let drag = 0.05 + 1.2 * alpha.sin() * alpha.sin();
+
+            // Heeled over, the sail presents less area to the wind.
+            let upright = transform.up().y.max(0.0);
+            let pressure = 0.5 * AIR_DENSITY * speed * speed * sails.area * sails.hoisted * upright;
+            let mut lift_dir = Vec3::Y.cross(flow);
+            if lift_dir.dot(heading) < 0.0 {
+                lift_dir = -lift_dir;
+            }
+            let sail_force = (flow * drag + lift_dir * lift) * pressure;
+            let arm = transform.rotation * sails.centre_of_effort;
+            force += sail_force;
+            torque += arm.cross(sail_force);
+
+            sails.boom_angle = -wind_side * boom;
+            sails.apparent_wind_angle = awa;
+        }
+        sails.apparent_wind_speed = speed;
+
+        if ship.submerged_volume > 0.0 {
+            let keel_arm = transform.rotation * sails.keel_centre;
+            let keel_velocity = velocity.linvel + velocity.angvel.cross(keel_arm);
+            let right = transform.right().as_vec3();
+            let leeway = right.dot(keel_velocity);
+            let keel_force =
+                -right * 0.5 * WATER_DENSITY * sails.keel_area * KEEL_LIFT * leeway * leeway.abs();
+            force += keel_force;
+            torque += keel_arm.cross(keel_force);
+
+            // Under engine the helm steers with prop wash instead (ship_movement).
+            if ship.propulsion == ShipPropulsion::Sail {
+                let way = heading.dot(hull_velocity);
+                torque.y += RUDDER_TORQUE * ship.current_steering * way * way.abs();
+            }
+        }
+
+        ext_force.force += force;
+        ext_force.torque += torque;
+    }
+}
+
+/// Mast, and a sail on a pivot that swings with the boom. Children inherit the ship's
+/// scale, so offsets are divided back out.
+fn spawn_sail_rig(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    query: Query<(Entity, &Transform, &Sails), Added<Sails>>,
+) {
+    for (ship_id, transform, sails) in query.iter() {
+        let inv_scale = 1.0 / transform.scale.x.max(0.01);
+        let mast = commands
+            .spawn((
+                Mesh3d(meshes.add(Cylinder::new(0.15, MAST_HEIGHT))),
+                MeshMaterial3d(materials.add(Color::srgb(0.45, 0.32, 0.2))),
+                Transform::from_translation(
+                    (sails.mast_step + Vec3::Y * MAST_HEIGHT * 0.5) * inv_scale,
+                )
+                .with_scale(Vec3::splat(inv_scale)),
+            ))
+            .id();
+        let cloth = commands
+            .spawn((
+                Mesh3d(meshes.add(Cuboid::new(0.05, 1.0, BOOM_LENGTH))),
+                MeshMaterial3d(materials.add(StandardMaterial {
+                    base_color: Color::srgb(0.93, 0.9, 0.82),
+                    perceptual_roughness: 0.9,
+                    cull_mode: None,
+                    ..default()
+                })),
+                Transform::from_xyz(0.0, 0.0, BOOM_LENGTH * 0.5),
+                Visibility::Hidden,
+                SailCloth,
+            ))
+            .id();
+        let pivot = commands
+            .spawn((
+                Transform::from_translation((sails.mast_step + Vec3::Y * 1.0) * inv_scale)
+                    .with_scale(Vec3::splat(inv_scale)),
+                Visibility::default(),
+                SailPivot,
+            ))
+            .add_child(cloth)
+            .id();
+        commands.entity(ship_id).add_children(&[mast, pivot]);
+    }
+}
+
+fn update_sail_rig(
+    sails_query: Query<(&Sails, &Children)>,
+    mut pivot_query: Query<(&mut Transform, &Children), With<SailPivot>>,
+    mut cloth_query: Query<(&mut Transform, &mut Visibility), (With<SailCloth>, Without<SailPivot>)>,
+) {
+    for (sails, children) in sails_query.iter() {
+        for child in children.iter() {
+            let Ok((mut pivot_tf, pivot_children)) = pivot_query.get_mut(child) else { continue };
+            pivot_tf.rotation = Quat::from_rotation_y(sails.boom_angle);
+            for cloth in pivot_children.iter() {
+                let Ok((mut cloth_tf, mut visibility)) = cloth_query.get_mut(cloth) else { continue };
+                let height = (MAST_HEIGHT - 1.0) * sails.hoisted;
+                cloth_tf.scale.y = height.max(0.01);
+                cloth_tf.translation.y = height * 0.5;
+                *visibility = if sails.hoisted > 0.01 {
+                    Visibility::Inherited
+                } else {
+                    Visibility::Hidden
+                };
+            }
+        }
+    }
+}
+
+fn spawn_sail_ui(mut commands: Commands) {
+    let (root_id, _) = spawn_hud_row(&mut commands, SAIL_SLOT, SailUiText);
+    commands.insert_resource(SailUiRoot(root_id));
+}
+
+fn update_sail_ui(
+    mode: Res<PlayerMode>,
+    ui: Res<SailUiRoot>,
+    ship_query: Query<(&Ship, &Sails)>,
+    mut visibility_query: Query<&mut Visibility>,
+    mut text_query: Query<&mut Text, With<SailUiText>>,
+) {
+    let Ok(mut root_vis) = visibility_query.get_mut(ui.0) else { return };
+    let Ok((ship, sails)) = ship_query.single() else {
+        *root_vis = Visibility::Hidden;
+        return;
+    };
+    if !mode.in_boat || ship.propulsion != ShipPropulsion::Sail {
+        *root_vis = Visibility::Hidden;
+        return;
+    }
+    *root_vis = Visibility::Visible;
+    let Ok(mut text) = text_query.single_mut() else { return };
+    let status = if sails.in_irons() { "  IN IRONS" } else { "" };
+    *text = Text::new(format!(
+        "Sail {:.0}%  Sheet {:.0}%  Wind {:.0} kn, {:.0}° off the bow{}  [W/S] hoist [Q/Z] trim",
+        sails.hoisted * 100.0,
+        sails.sheet * 100.0,
+        sails.apparent_wind_speed * 1.944,
+        sails.apparent_wind_angle.to_degrees(),
+        status,
+    ));
+}
@@ -4,6 +4,7 @@ use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
 
 use crate::character::MarineCharacter;
+use crate::cradle::{Cradle, RestoreDock};
 use crate::player::PlayerCamera;
 use serde::{Deserialize, Serialize};
 
@@ -13,7 +14,14 @@ use crate::settings::InputBindings;
 use crate::game_state::GameState;
 use crate::player::PlayerMode;
 use crate::ship::Ship;
-use crate::winch::WinchState;
+use crate::ship_hull::ShipHull;
+use crate::ship_stores::ShipStores;
+use crate::ballast::Ballast;
+use crate::sub_hull::SubHull;
+use crate::sub_power::SubBattery;
+use crate::tide::Tide;
+use crate::winch::{RestoreTether, WinchState};
+use crate::wreck::{RestoreWrecks, Wreck, WreckCargo};
 
 const SAVE_PATH: &str = "save.ron";
 
@@ -27,8 +35,64 @@ pub struct SaveData {
     pub character: EntitySave,
     pub player_mode: PlayerModeSave,
     pub winch_cable_length: f32,
+    /// Missing in older saves, which always had the cable rigged.
+    #[serde(default = "cable_rigged")]
+    pub winch_tethered: bool,
     #[serde(default)]
     pub inventory_items: Vec<String>,
+    #[serde(default)]
+    pub tide_phase: f32,
+    /// Missing in older saves: the hull loads intact.
+    #[serde(default)]
+    pub ship_hull: Option<ShipHullSave>,
+    /// Missing in older saves: the stores keep what the ship started with.
+    #[serde(default)]
+    pub ship_stores: Option<ShipStoresSave>,
+    /// Lost ships on the seabed and the cargo still lying around them.
+    #[serde(default)]
+    pub wrecks: Vec<WreckSave>,
+    /// Missing in older saves: the sub's hull loads intact.
+    #[serde(default)]
+    pub sub_hull_integrity: Option<f32>,
+    /// Missing in older saves: the sub's battery loads full.
+    #[serde(default)]
+    pub sub_battery_charge: Option<f32>,
+    /// Missing in older saves: the tanks load dry and level.
+    #[serde(default)]
+    pub sub_ballast: Option<SubBallastSave>,
+    /// Missing in older saves, which had the sub afloat where she was saved.
+    #[serde(default)]
+    pub sub_docked: bool,
+}
+
+fn cable_rigged() -> bool {
+    true
+}
+
+#[derive(Serialize, Deserialize, Default, Clone, Copy)]
+pub struct SubBallastSave {
+    pub fill: f32,
+    pub trim: f32,
+}
+
+#[derive(Serialize, Deserialize, Default, Clone, Copy)]
+pub struct ShipHullSave {
+    pub integrity: f32,
+    pub flooded: f32,
+}
+
+#[derive(Serialize, Deserialize, Default, Clone, Copy)]
+pub struct ShipStoresSave {
+    pub fuel: f32,
+    pub oxygen_tanks: u32,
+    pub repair_wood: u32,
+}
+
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct WreckSave {
+    pub translation: [f32; 3],
+    pub rotation: [f32; 4],
+    pub cargo: Vec<String>,
 }
 
 #[derive(Serialize, Deserialize, Default, Clone, Copy)]
@@ -70,11 +134,18 @@ fn save_system(
     keyboard: Res<ButtonInput<KeyCode>>,
     bindings: Res<InputBindings>,
     ship_query: Query<(&Transform, &Velocity), With<Ship>>,
+    hull_query: Query<&ShipHull>,
+    stores_query: Query<&ShipStores>,
+    sub_state_query: Query<(&SubHull, &SubBattery, &Ballast)>,
     sub_query: Query<(&Transform, &Velocity), With<Submersible>>,
     character_query: Query<&Transform, With<MarineCharacter>>,
     mode: Res<PlayerMode>,
     winch: Res<WinchState>,
+    cradle: Res<Cradle>,
     inventory: Res<Inventory>,
+    tide: Res<Tide>,
+    wreck_query: Query<(Entity, &Transform), With<Wreck>>,
+    cargo_query: Query<&WreckCargo>,
 ) {
     if !keyboard.just_pressed(bindings.save) {
         return;
@@ -132,7 +203,37 @@ fn save_system(
             in_submersible: mode.in_submersible,
         },
         winch_cable_length: winch.cable_length,
+        winch_tethered: winch.tethered,
         inventory_items: inventory.items.clone(),
+        tide_phase: tide.phase,
+        ship_hull: hull_query.iter().next().map(|h| ShipHullSave {
+            integrity: h.integrity,
+            flooded: h.flooded,
+        }),
+        ship_stores: stores_query.iter().next().map(|s| ShipStoresSave {
+            fuel: s.fuel,
+            oxygen_tanks: s.oxygen_tanks,
+            repair_wood: s.repair_wood,
+        }),
+        wrecks: wreck_query
+            .iter()
+            .map(|(wreck_id, t)| WreckSave {
+                translation: t.translation.to_array(),
+                rotation: t.rotation.to_array(),
+                cargo: cargo_query
+                    .iter()
+                    .filter(|c| c.wreck == wreck_id)
+                    .map(|c| c.item_id.clone())
+                    .collect(),
+            })
+            .collect(),
+        sub_hull_integrity: sub_state_query.iter().next().map(|(h, _, _)| h.integrity),
+        sub_battery_charge: sub_state_query.iter().next().map(|(_, b, _)| b.charge),
+        sub_ballast: sub_state_query.iter().next().map(|(_, _, b)| SubBallastSave {
+            fill: b.fill,
+            trim: b.trim,
+        }),
+        sub_docked: cradle.is_docked(),
     };
 
     if let Ok(s) = ron::ser::to_string_pretty(&data, ron::ser::PrettyConfig::default()) {
@@ -196,6 +297,46 @@ fn apply_load_system(world: &mut World) {
     if let Some(mut inventory) = world.get_resource_mut::<Inventory>() {
         inventory.items = data.inventory_items.clone();
     }
+    if let Some(mut tide) = world.get_resource_mut::<Tide>() {
+        tide.set_phase(data.tide_phase);
+    }
+    if let Some(saved) = data.ship_hull {
+        let mut hull_query = world.query::<&mut ShipHull>();
+        if let Some(mut hull) = hull_query.iter_mut(world).next() {
+            hull.integrity = saved.integrity.clamp(0.0, hull.max_integrity);
+            hull.flooded = saved.flooded.max(0.0);
+        }
+    }
+    if let Some(saved) = data.ship_stores {
+        let mut stores_query = world.query::<&mut ShipStores>();
+        if let Some(mut stores) = stores_query.iter_mut(world).next() {
+            stores.fuel = saved.fuel.clamp(0.0, stores.max_fuel);
+            stores.oxygen_tanks = saved.oxygen_tanks.min(stores.max_oxygen_tanks);
+            stores.repair_wood = saved.repair_wood.min(stores.max_repair_wood);
+        }
+    }
+    if let Some(integrity) = data.sub_hull_integrity {
+        let mut sub_hull_query = world.query::<&mut SubHull>();
+        if let Some(mut hull) = sub_hull_query.iter_mut(world).next() {
+            hull.integrity = integrity.clamp(0.0, hull.max_integrity);
+        }
+    }
+    if let Some(charge) = data.sub_battery_charge {
+        let mut battery_query = world.query::<&mut SubBattery>();
+        if let Some(mut battery) = battery_query.iter_mut(world).next() {
+            battery.charge = charge.clamp(0.0, battery.capacity);
+        }
+    }
+    if let Some(saved) = data.sub_ballast {
+        let mut ballast_query = world.query::<&mut Ballast>();
+        if let Some(mut ballast) = ballast_query.iter_mut(world).next() {
+            ballast.fill = saved.fill.clamp(0.0, 1.0);
+            ballast.trim = saved.trim.clamp(-1.0, 1.0);
+        }
+    }
+    world.insert_resource(RestoreDock(Some(data.sub_docked)));
+    world.insert_resource(RestoreTether(Some(data.winch_tethered)));
+    world.insert_resource(RestoreWrecks(Some(data.wrecks)));
 
     let mut camera_query = world.query_filtered::<Entity, With<PlayerCamera>>();
     let mut character_entity_query = world.query_filtered::<Entity, With<MarineCharacter>>();
@@ -5,7 +5,7 @@ use bevy::prelude::*;
 use bevy::scene::SceneRoot;
 
 use crate::islands::{IslandCollider, SafeIsland};
-use crate::ocean::SEA_LEVEL;
+use crate::ocean::MEAN_SEA_LEVEL;
 use crate::world::MAP_FLOOR_Y;
 
 /// Scatter props around island bases and on seafloor.
@@ -78,7 +78,7 @@ fn spawn_scatter(
                 Vec3::splat(scale)
             };
             // Rocks at shore (island-relative): just above waterline
-            let rock_y = center.y + SEA_LEVEL + 1.5;
+            let rock_y = center.y + MEAN_SEA_LEVEL + 1.5;
             commands.spawn((
                 Mesh3d(mesh),
                 MeshMaterial3d(rock_mat.clone()),
@@ -96,7 +96,7 @@ fn spawn_scatter(
         // Scatter seaweed (tapered capsules) in shallow water (~0.5m below surface)
         if radius > 15.0 {
             let seaweed_count = (radius * 0.3) as usize;
-            let seaweed_y = center.y + SEA_LEVEL + 0.5;
+            let seaweed_y = center.y + MEAN_SEA_LEVEL + 0.5;
             for i in 0..seaweed_count {
                 let angle = (i as f32 * 3.7) % std::f32::consts::TAU;
                 let dist = radius * 0.6 + (i as f32 * 0.2 % 0.6);
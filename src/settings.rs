@@ -17,6 +17,33 @@ pub struct InputBindings {
     pub descend: KeyCode,
     pub reel_in: KeyCode,
     pub reel_out: KeyCode,
+    pub repair: KeyCode,
+    pub sheet_in: KeyCode,
+    pub sheet_out: KeyCode,
+    pub engine: KeyCode,
+    pub resupply: KeyCode,
+    pub anchor: KeyCode,
+    pub autopilot: KeyCode,
+    pub mark_waypoint: KeyCode,
+    pub heading_left: KeyCode,
+    pub heading_right: KeyCode,
+    pub floodlight: KeyCode,
+    pub ballast_flood: KeyCode,
+    pub ballast_blow: KeyCode,
+    pub trim_bow_down: KeyCode,
+    pub trim_bow_up: KeyCode,
+    pub emergency_blow: KeyCode,
+    pub roll_left: KeyCode,
+    pub roll_right: KeyCode,
+    pub stabiliser: KeyCode,
+    pub arm_left: KeyCode,
+    pub arm_right: KeyCode,
+    pub arm_up: KeyCode,
+    pub arm_down: KeyCode,
+    pub arm_extend: KeyCode,
+    pub arm_retract: KeyCode,
+    pub claw: KeyCode,
+    pub sonar_ping: KeyCode,
     pub pause: KeyCode,
     pub menu_start: KeyCode,
 }
@@ -36,6 +63,33 @@ impl Default for InputBindings {
             descend: KeyCode::ShiftLeft,
             reel_in: KeyCode::KeyR,
             reel_out: KeyCode::KeyT,
+            repair: KeyCode::KeyH,
+            sheet_in: KeyCode::KeyQ,
+            sheet_out: KeyCode::KeyZ,
+            engine: KeyCode::KeyG,
+            resupply: KeyCode::KeyF,
+            anchor: KeyCode::KeyX,
+            autopilot: KeyCode::KeyP,
+            mark_waypoint: KeyCode::KeyM,
+            heading_left: KeyCode::BracketLeft,
+            heading_right: KeyCode::BracketRight,
+            floodlight: KeyCode::KeyL,
+            ballast_flood: KeyCode::KeyV,
+            ballast_blow: KeyCode::KeyB,
+            trim_bow_down: KeyCode::Period,
+            trim_bow_up: KeyCode::Comma,
+            emergency_blow: KeyCode::Backspace,
+            roll_left: KeyCode::KeyJ,
+            roll_right: KeyCode::KeyK,
+            stabiliser: KeyCode::KeyO,
+            arm_left: KeyCode::ArrowLeft,
+            arm_right: KeyCode::ArrowRight,
+            arm_up: KeyCode::ArrowUp,
+            arm_down: KeyCode::ArrowDown,
+            arm_extend: KeyCode::KeyU,
+            arm_retract: KeyCode::KeyY,
+            claw: KeyCode::KeyC,
+            sonar_ping: KeyCode::KeyN,
             pause: KeyCode::Escape,
             menu_start: KeyCode::Enter,
         }
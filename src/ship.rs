@@ -1,4 +1,4 @@
-//! Ship with buoyancy, engine. Rapier Dynamic + ExternalForce.
+//! Ship with hull-volume buoyancy, engine or sails. Rapier Dynamic + ExternalForce.
 
 use bevy::gltf::GltfAssetLabel;
 use bevy::input::mouse::AccumulatedMouseMotion;
@@ -6,32 +6,124 @@ use bevy::prelude::*;
 use bevy::scene::SceneRoot;
 
 use bevy_rapier3d::prelude::*;
+use crate::autopilot::Autopilot;
+use crate::character::MovingPlatform;
+use crate::currents::OceanCurrents;
 use crate::game_state::GameState;
 use crate::interaction::{Interactable, InteractKind};
 use crate::settings::InputBindings;
 use crate::ocean::OceanSolver;
+use crate::sailing::Sails;
+use crate::ship_stores::ShipStores;
+use crate::ship_hull::ShipHull;
 use crate::player::{PlayerMode, VEHICLE_ENTER_RANGE};
+use crate::wake::WakeSource;
+use crate::weather::Weather;
 use crate::world::{MAP_SCALE_FROM_LEGACY, SPAWN_ISLAND_X, SPAWN_ISLAND_Z};
 
 /// Ship anchored near Safe Island: offset from island center.
 const SHIP_ANCHOR_OFFSET: Vec3 = Vec3::new(3.0, 0.0, -2.0);
 
-/// Hull corners for buoyancy (rowboat ≈ 2.5 scale).
-const PONTOON_OFFSETS: [Vec3; 4] = [
-    Vec3::new(-0.9, -0.25, -1.5),
-    Vec3::new(0.9, -0.25, -1.5),
-    Vec3::new(-0.9, -0.25, 1.5),
-    Vec3::new(0.9, -0.25, 1.5),
-];
+/// Wind force per (m/s)² of apparent wind on the topsides and rig. Roughly ½ρ·C_d·A for
+/// ~20 m² of frontal area; the sail's own drag is in `sail_forces`.
+const WINDAGE: f32 = 12.0;
+
+/// Model and collider scale.
+pub const SHIP_SCALE: f32 = 2.5;
+
+/// Collider half extents before `SHIP_SCALE`.
+pub const HULL_HALF_EXTENTS: Vec3 = Vec3::new(2.5, 0.7, 4.0);
+
+/// Hull voxel grid (beam, depth, length).
+const HULL_DIVISIONS: UVec3 = UVec3::new(6, 3, 10);
+
+/// Sea water density (kg/m³).
+pub const WATER_DENSITY: f32 = 1025.0;
+
+/// Matches Rapier's default gravity.
+pub const GRAVITY: f32 = 9.81;
+
+/// What drives the ship: the sails, or the auxiliary engine (G) while there is fuel.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ShipPropulsion {
+    /// `engine_power` thrust from W/S throttle, `turn_speed` yaw at any speed. Burns fuel.
+    Engine,
+    /// Wind on the sails (sailing.rs); the rudder only bites with way on.
+    Sail,
+}
 
 #[derive(Component)]
 pub struct Ship {
-    pub float_force: f32,
-    pub water_drag: f32,
+    pub propulsion: ShipPropulsion,
+    /// Drag per m³ submerged, per m/s of water flow across the hull (N·s/m⁴).
+    pub lateral_drag: f32,
+    /// As `lateral_drag`, along the keel. Low: the hull is shaped to slip forward.
+    pub forward_drag: f32,
+    /// As `lateral_drag`, heaving up and down. Damps bobbing.
+    pub vertical_drag: f32,
+    /// Engine thrust at full throttle (N).
     pub engine_power: f32,
+    /// Prop-wash yaw torque at full helm under engine (N·m).
     pub turn_speed: f32,
     pub current_throttle: f32,
     pub current_steering: f32,
+    /// Yaw torque from mouse look, sampled per frame and held across fixed steps.
+    pub current_look: f32,
+    /// Displaced volume (m³) at the last buoyancy step.
+    pub submerged_volume: f32,
+    /// Depth of the keel below the surface at the last buoyancy step (m).
+    pub draft: f32,
+    /// World-space centre of buoyancy at the last buoyancy step.
+    pub centre_of_buoyancy: Vec3,
+}
+
+/// Hull shape as voxel centres in body space (m, scale applied). Each voxel is `voxel_size`.
+#[derive(Component)]
+pub struct HullVolume {
+    pub voxels: Vec<Vec3>,
+    pub voxel_size: Vec3,
+    /// Half extents (m) of the box the hull was voxelised in.
+    pub half_extents: Vec3,
+    /// Body-space keel point, bottom centre of the hull.
+    pub keel: Vec3,
+}
+
+impl HullVolume {
+    /// Voxelises a round-bilged hull inside a box: beam narrows toward bow, stern and keel.
+    pub fn rowboat(half_extents: Vec3, divisions: UVec3) -> Self {
+        let voxel_size = 2.0 * half_extents / divisions.as_vec3();
+        let mut voxels = Vec::new();
+        for iz in 0..divisions.z {
+            for iy in 0..divisions.y {
+                for ix in 0..divisions.x {
+                    let centre = -half_extents + (UVec3::new(ix, iy, iz).as_vec3() + 0.5) * voxel_size;
+                    let along = centre.z / half_extents.z;
+                    let height = (centre.y + half_extents.y) / (2.0 * half_extents.y);
+                    let beam = (1.0 - 0.6 * along * along) * (0.45 + 0.55 * height);
+                    if (centre.x / half_extents.x).abs() <= beam {
+                        voxels.push(centre);
+                    }
+                }
+            }
+        }
+        Self {
+            voxels,
+            voxel_size,
+            half_extents,
+            keel: Vec3::new(0.0, -half_extents.y, 0.0),
+        }
+    }
+
+    /// Total hull volume (m³): the most water it can displace, or take on.
+    pub fn volume(&self) -> f32 {
+        self.voxels.len() as f32 * self.voxel_size.x * self.voxel_size.y * self.voxel_size.z
+    }
+
+    /// Voxels in the top layer, where green water comes aboard.
+    pub fn deck_voxels(&self) -> impl Iterator<Item = &Vec3> {
+        let deck = self.half_extents.y - self.voxel_size.y;
+        self.voxels.iter().filter(move |v| v.y > deck)
+    }
 }
 
 pub struct ShipPlugin;
@@ -42,19 +134,33 @@ impl Plugin for ShipPlugin {
             .add_systems(
                 Update,
                 (
-                    ship_buoyancy.run_if(in_state(GameState::Playing)),
                     ship_input
                         .run_if(in_state(GameState::Playing))
                         .run_if(|mode: Res<PlayerMode>| mode.in_boat),
-                    ship_mouse_look
-                        .run_if(in_state(GameState::Playing))
-                        .run_if(|mode: Res<PlayerMode>| mode.in_boat),
-                    ship_movement.run_if(in_state(GameState::Playing)),
+                    ship_mouse_look.run_if(in_state(GameState::Playing)),
                 ),
+            )
+            .add_systems(
+                FixedUpdate,
+                // Chained: each adds to ExternalForce, and a fixed order keeps the sum bit-identical.
+                (ship_buoyancy, ship_windage, ship_movement)
+                    .chain()
+                    .before(PhysicsSet::SyncBackend)
+                    .run_if(in_state(GameState::Playing)),
             );
     }
 }
 
+/// Where the ship starts, and where a replacement is waiting after she's lost.
+pub fn ship_spawn_transform() -> Transform {
+    Transform::from_xyz(
+        SPAWN_ISLAND_X + SHIP_ANCHOR_OFFSET.x * MAP_SCALE_FROM_LEGACY,
+        -0.5,
+        SPAWN_ISLAND_Z + SHIP_ANCHOR_OFFSET.z * MAP_SCALE_FROM_LEGACY,
+    )
+    .with_scale(Vec3::splat(SHIP_SCALE))
+}
+
 fn spawn_ship(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
@@ -62,28 +168,36 @@ fn spawn_ship(
     let scene = asset_server.load(GltfAssetLabel::Scene(0).from_asset("models/boat-row-small.glb"));
     commands.spawn((
         RigidBody::Dynamic,
-        Collider::cuboid(2.5, 0.7, 4.0),
+        Collider::cuboid(HULL_HALF_EXTENTS.x, HULL_HALF_EXTENTS.y, HULL_HALF_EXTENTS.z),
         ColliderMassProperties::Density(150.0),
         GravityScale(1.0),
+        // Hull drag models the water; this only stands in for air resistance.
         Damping {
-            linear_damping: 2.0,
-            angular_damping: 3.0,
+            linear_damping: 0.02,
+            angular_damping: 0.5,
         },
         ExternalForce::default(),
+        Velocity::default(),
         SceneRoot(scene),
-        Transform::from_xyz(
-            SPAWN_ISLAND_X + SHIP_ANCHOR_OFFSET.x * MAP_SCALE_FROM_LEGACY,
-            -0.5,
-            SPAWN_ISLAND_Z + SHIP_ANCHOR_OFFSET.z * MAP_SCALE_FROM_LEGACY,
-        ).with_scale(Vec3::splat(2.5)),
+        ship_spawn_transform(),
         Ship {
-            float_force: 22000.0,
-            water_drag: 2.0,
-            engine_power: 22000.0,
-            turn_speed: 3500.0,
+            propulsion: ShipPropulsion::Sail,
+            lateral_drag: 1500.0,
+            forward_drag: 40.0,
+            vertical_drag: 2500.0,
+            engine_power: 25000.0,
+            turn_speed: 1.0e6,
             current_throttle: 0.0,
             current_steering: 0.0,
+            current_look: 0.0,
+            submerged_volume: 0.0,
+            draft: 0.0,
+            centre_of_buoyancy: Vec3::ZERO,
         },
+        HullVolume::rowboat(HULL_HALF_EXTENTS * SHIP_SCALE, HULL_DIVISIONS),
+        (ShipHull::bundle(), ShipStores::bundle(), MovingPlatform, Autopilot::default()),
+        Sails::rowboat(),
+        WakeSource::new(1.5, 0.12),
         Interactable {
             kind: InteractKind::EnterShip,
             range: VEHICLE_ENTER_RANGE,
@@ -91,73 +205,107 @@ fn spawn_ship(
     ));
 }
 
+/// Archimedes per voxel: each pushes up by the weight of water it displaces, at its own
+/// centre, so force and torque sum to lift at the centre of buoyancy. Water flowing past
+/// a wetted voxel (orbital motion plus the surface current) drags it with per-axis
+/// coefficients in hull space.
 fn ship_buoyancy(
     ocean: Res<OceanSolver>,
-    mut query: Query<(&Transform, &Ship, &mut ExternalForce), With<Ship>>,
+    currents: Res<OceanCurrents>,
+    mut query: Query<(&Transform, &Velocity, &mut Ship, &HullVolume, &mut ExternalForce)>,
 ) {
-    for (transform, ship, mut ext_force) in query.iter_mut() {
-        let mut buoyancy = Vec3::ZERO;
-        let mut pontoons_underwater = 0u32;
-
-        for offset in PONTOON_OFFSETS {
-            let pos = transform.translation + transform.rotation * offset;
-            let wave_height = ocean.wave_height_at(pos);
-
-            if pos.y < wave_height {
-                pontoons_underwater += 1;
-                let depth = wave_height - pos.y;
-                buoyancy.y += depth * ship.float_force;
+    for (transform, velocity, mut ship, hull, mut ext_force) in query.iter_mut() {
+        let voxel_volume = hull.voxel_size.x * hull.voxel_size.y * hull.voxel_size.z;
+        let to_hull = transform.rotation.inverse();
+        let drag = Vec3::new(ship.lateral_drag, ship.vertical_drag, ship.forward_drag);
+        let mut force = Vec3::ZERO;
+        let mut torque = Vec3::ZERO;
+        let mut submerged = 0.0;
+        let mut moment = Vec3::ZERO;
+        // Sample just under the surface; above it the field is zero.
+        let near_surface =
+            Vec3::new(transform.translation.x, ocean.sea_level - 0.5, transform.translation.z);
+        let current = currents.sample(near_surface, ocean.sea_level);
+
+        for voxel in &hull.voxels {
+            let arm = transform.rotation * *voxel;
+            let pos = transform.translation + arm;
+            let surface = ocean.surface_at(pos);
+            let bottom = pos.y - 0.5 * hull.voxel_size.y;
+            let fraction = ((surface.position.y - bottom) / hull.voxel_size.y).clamp(0.0, 1.0);
+            if fraction <= 0.0 {
+                continue;
             }
+            let volume = voxel_volume * fraction;
+            let hull_velocity = velocity.linvel + velocity.angvel.cross(arm);
+            let relative = to_hull * (surface.velocity + current - hull_velocity);
+            let voxel_force = Vec3::Y * WATER_DENSITY * GRAVITY * volume
+                + transform.rotation * (drag * relative) * volume;
+
+            force += voxel_force;
+            torque += arm.cross(voxel_force);
+            submerged += volume;
+            moment += pos * volume;
         }
 
-        ext_force.torque = Vec3::ZERO;
-        if pontoons_underwater > 0 {
-            ext_force.force = buoyancy;
+        let keel = transform.translation + transform.rotation * hull.keel;
+        ship.draft = (ocean.wave_height_at(keel) - keel.y).max(0.0);
+        ship.submerged_volume = submerged;
+        ship.centre_of_buoyancy = if submerged > 0.0 {
+            moment / submerged
         } else {
-            ext_force.force = Vec3::ZERO;
-        }
+            transform.translation
+        };
+        ext_force.force = force;
+        ext_force.torque = torque;
     }
 }
 
-fn ship_movement(
-    ocean: Res<OceanSolver>,
-    mut query: Query<(&Ship, &Transform, &mut ExternalForce), With<Ship>>,
-    time: Res<Time>,
+/// Wind pushes the boat downwind; strength scales with apparent wind squared.
+fn ship_windage(
+    weather: Res<Weather>,
+    mut query: Query<(&Velocity, &mut ExternalForce), With<Ship>>,
 ) {
-    for (ship, transform, mut ext_force) in query.iter_mut() {
-        let mut pontoons_underwater = 0u32;
-        for offset in PONTOON_OFFSETS {
-            let pos = transform.translation + transform.rotation * offset;
-            let wave_height = ocean.wave_height_at(pos);
-            if pos.y < wave_height {
-                pontoons_underwater += 1;
-            }
-        }
+    let wind = weather.wind();
+    for (velocity, mut ext_force) in query.iter_mut() {
+        let apparent = wind - Vec3::new(velocity.linvel.x, 0.0, velocity.linvel.z);
+        ext_force.force += apparent * apparent.length() * WINDAGE;
+    }
+}
 
-        if pontoons_underwater > 0 {
-            let forward = transform.forward();
-            let thrust = forward * ship.engine_power * ship.current_throttle * time.delta_secs() * 0.001;
-            let torque_y = ship.turn_speed * ship.current_steering * time.delta_secs() * 0.001;
+/// Engine thrust and prop-wash steering while there is fuel and the prop is in the water.
+pub(crate) fn ship_movement(
+    mut query: Query<(&Ship, &Transform, &mut ShipStores, &mut ExternalForce)>,
+    time: Res<Time>,
+) {
+    for (ship, transform, mut stores, mut ext_force) in query.iter_mut() {
+        let running = ship.propulsion == ShipPropulsion::Engine && stores.fuel > 0.0;
+        if running && ship.submerged_volume > 0.0 {
+            stores.burn(ship.current_throttle.abs() * time.delta_secs());
+            let thrust = transform.forward() * ship.engine_power * ship.current_throttle;
+            let torque_y = ship.turn_speed * ship.current_steering;
             ext_force.force += thrust;
             ext_force.torque += Vec3::new(0.0, torque_y, 0.0);
         }
+        ext_force.torque.y += ship.current_look;
     }
 }
 
 fn ship_mouse_look(
     mouse_motion: Res<AccumulatedMouseMotion>,
+    mode: Res<PlayerMode>,
     time: Res<Time>,
-    mut query: Query<&mut ExternalForce, With<Ship>>,
+    mut query: Query<&mut Ship>,
 ) {
-    let delta_x = mouse_motion.delta.x;
-    if delta_x == 0.0 {
-        return;
-    }
     const SENSITIVITY: f32 = 0.002;
     let dt = time.delta_secs().max(0.001);
-    let ang = -delta_x * SENSITIVITY / dt;
-    for mut ext_force in query.iter_mut() {
-        ext_force.torque.y += ang * 5000.0;
+    let ang = if mode.in_boat {
+        -mouse_motion.delta.x * SENSITIVITY / dt
+    } else {
+        0.0
+    };
+    for mut ship in query.iter_mut() {
+        ship.current_look = ang * 5000.0;
     }
 }
 
@@ -167,6 +315,12 @@ fn ship_input(
     mut query: Query<&mut Ship, With<Ship>>,
 ) {
     for mut ship in query.iter_mut() {
+        if keyboard.just_pressed(bindings.engine) {
+            ship.propulsion = match ship.propulsion {
+                ShipPropulsion::Engine => ShipPropulsion::Sail,
+                ShipPropulsion::Sail => ShipPropulsion::Engine,
+            };
+        }
         ship.current_throttle = if keyboard.pressed(bindings.forward) {
             1.0
         } else if keyboard.pressed(bindings.back) {
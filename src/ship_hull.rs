@@ -0,0 +1,297 @@
+//! Ship hull integrity – damage from impacts and breaking seas, flooding, and repair.
+//!
+//! Hard contacts with islands and rocks (Rapier contact force events) and green water
+//! over the deck wear the hull down. A damaged hull leaks; floodwater is added mass (summed
+//! with the stores in ship_stores) that sloshes to the low side and eats into reserve
+//! buoyancy until the deck goes under.
+//! Patch it with Repair Wood (H) from aboard or alongside (proj.md Phase 4).
+
+use bevy::ecs::component::Mutable;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+use crate::artifacts::{Inventory, REPAIR_WOOD};
+use crate::character::MarineCharacter;
+use crate::game_state::GameState;
+use crate::hud::{spawn_hud_row, SHIP_HULL_SLOT};
+use crate::interaction::{Interactable, InteractKind};
+use crate::ocean::OceanSolver;
+use crate::player::{PlayerMode, VEHICLE_ENTER_RANGE};
+use crate::settings::InputBindings;
+use crate::ship::{HullVolume, Ship};
+use crate::ship_stores::ShipStores;
+use crate::sim_clock::SIM_DT;
+use crate::world::{SPAWN_ISLAND_X, SPAWN_ISLAND_Z};
+
+/// Full hull integrity.
+const MAX_INTEGRITY: f32 = 100.0;
+
+/// Contact force (N) below which the hull takes no damage: resting against a dock or
+/// nudging a rock. Roughly the ship's own weight.
+const COLLISION_THRESHOLD: f32 = 1.5e6;
+
+/// Integrity lost per N·s of contact impulse above the threshold.
+const COLLISION_DAMAGE: f32 = 1.0e-4;
+
+/// Water speed over the deck (m/s) that starts breaking things.
+const WAVE_DAMAGE_SPEED: f32 = 1.0;
+
+/// Integrity lost per second per m/s above `WAVE_DAMAGE_SPEED`, with the whole deck awash.
+const WAVE_DAMAGE: f32 = 2.0;
+
+/// Leak (m³/s) through a hull at zero integrity. Scales with damage squared.
+const LEAK_RATE: f32 = 4.0;
+
+/// Green water (m³/s) coming aboard with the whole deck awash.
+const GREEN_WATER_RATE: f32 = 20.0;
+
+/// Water (m³/s) the crew bails and the scuppers drain.
+const BAIL_RATE: f32 = 0.5;
+
+/// Integrity restored by one plank of repair wood.
+const REPAIR_PER_PLANK: f32 = 25.0;
+
+/// Planks stacked on Safe Island at start.
+const WOOD_PILE_COUNT: usize = 4;
+
+#[derive(Component)]
+pub struct ShipHull {
+    pub integrity: f32,
+    pub max_integrity: f32,
+    /// Water aboard (m³).
+    pub flooded: f32,
+    /// Body-space centre of the floodwater (m), low on the side the hull has rolled to.
+    pub flood_centre: Vec3,
+}
+
+/// A hull that dents under hard contacts. Register `collision_damage::<H>` for it and put
+/// `H::contact_events()` on its body.
+pub trait ContactDamage: Component<Mutability = Mutable> {
+    /// Contact force (N) the hull shrugs off.
+    const COLLISION_THRESHOLD: f32;
+    /// Integrity lost per N·s of contact impulse above the threshold.
+    const COLLISION_DAMAGE: f32;
+
+    fn take_damage(&mut self, amount: f32);
+
+    /// Rapier contact force events, reported only above the threshold.
+    fn contact_events() -> impl Bundle {
+        (
+            ActiveEvents::CONTACT_FORCE_EVENTS,
+            ContactForceEventThreshold(Self::COLLISION_THRESHOLD),
+        )
+    }
+}
+
+impl ContactDamage for ShipHull {
+    const COLLISION_THRESHOLD: f32 = COLLISION_THRESHOLD;
+    const COLLISION_DAMAGE: f32 = COLLISION_DAMAGE;
+
+    fn take_damage(&mut self, amount: f32) {
+        self.damage(amount);
+    }
+}
+
+impl ShipHull {
+    /// Hull state plus the Rapier contact events it needs on the ship body.
+    pub fn bundle() -> impl Bundle {
+        (
+            ShipHull {
+                integrity: MAX_INTEGRITY,
+                max_integrity: MAX_INTEGRITY,
+                flooded: 0.0,
+                flood_centre: Vec3::ZERO,
+            },
+            Self::contact_events(),
+        )
+    }
+
+    pub fn damage(&mut self, amount: f32) {
+        self.integrity = (self.integrity - amount).max(0.0);
+    }
+
+    /// 0 = intact, 1 = holed.
+    pub fn damage_fraction(&self) -> f32 {
+        1.0 - self.integrity / self.max_integrity
+    }
+}
+
+#[derive(Component)]
+struct HullUiText;
+
+#[derive(Resource)]
+struct HullUiRoot {
+    root: Entity,
+    text: Entity,
+}
+
+pub struct ShipHullPlugin;
+
+impl Plugin for ShipHullPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, (spawn_wood_pile, spawn_hull_ui))
+            .add_systems(
+                FixedUpdate,
+                (
+                    collision_damage::<ShipHull>,
+                    flood_hull.before(PhysicsSet::SyncBackend),
+                )
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                Update,
+                (repair_hull, update_hull_ui).run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+/// Impulse above the threshold, from every hard contact the hull made this step.
+pub fn collision_damage<H: ContactDamage>(
+    mut events: MessageReader<ContactForceEvent>,
+    mut hull_query: Query<&mut H>,
+) {
+    for event in events.read() {
+        for collider in [event.collider1, event.collider2] {
+            if let Ok(mut hull) = hull_query.get_mut(collider) {
+                let excess = (event.total_force_magnitude - H::COLLISION_THRESHOLD).max(0.0);
+                hull.take_damage(excess * SIM_DT * H::COLLISION_DAMAGE);
+            }
+        }
+    }
+}
+
+/// Green water over the deck batters the hull and pours in; breaches leak while afloat.
+/// Floodwater sits low in the hull on the side it has rolled to; ship_stores adds its mass.
+pub(crate) fn flood_hull(
+    ocean: Res<OceanSolver>,
+    mut query: Query<(&Transform, &Velocity, &Ship, &HullVolume, &mut ShipHull)>,
+) {
+    for (transform, velocity, ship, hull_volume, mut hull) in query.iter_mut() {
+        let mut deck_points = 0u32;
+        let mut awash = 0.0;
+        let mut battering = 0.0;
+        for voxel in hull_volume.deck_voxels() {
+            deck_points += 1;
+            let arm = transform.rotation * *voxel;
+            let pos = transform.translation + arm;
+            let surface = ocean.surface_at(pos);
+            let deck = pos.y + 0.5 * hull_volume.voxel_size.y;
+            if surface.position.y <= deck {
+                continue;
+            }
+            let depth = ((surface.position.y - deck) / hull_volume.voxel_size.y).min(1.0);
+            let rush = (surface.velocity - velocity.linvel - velocity.angvel.cross(arm)).length();
+            awash += depth;
+            battering += depth * (rush - WAVE_DAMAGE_SPEED).max(0.0);
+        }
+        let deck_points = deck_points.max(1) as f32;
+        let awash = awash / deck_points;
+        hull.damage(battering / deck_points * WAVE_DAMAGE * SIM_DT);
+
+        let damage = hull.damage_fraction();
+        let leak = if ship.submerged_volume > 0.0 { LEAK_RATE * damage * damage } else { 0.0 };
+        let inflow = leak + awash * GREEN_WATER_RATE - BAIL_RATE;
+        hull.flooded = (hull.flooded + inflow * SIM_DT).clamp(0.0, hull_volume.volume());
+
+        let half = hull_volume.half_extents;
+        let fill = hull.flooded / hull_volume.volume().max(0.01);
+        let down = transform.rotation.inverse() * Vec3::NEG_Y;
+        hull.flood_centre = Vec3::new(
+            down.x * half.x * 0.6,
+            half.y * (fill - 1.0),
+            down.z * half.z * 0.6,
+        );
+    }
+}
+
+/// H spends a plank on the hull, from the helm or standing within reach of the boat.
+/// The ship's wood locker goes first, then planks carried in the inventory.
+fn repair_hull(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<InputBindings>,
+    mode: Res<PlayerMode>,
+    mut inventory: ResMut<Inventory>,
+    character_query: Query<&Transform, With<MarineCharacter>>,
+    mut ship_query: Query<(&Transform, &mut ShipHull, &mut ShipStores), Without<MarineCharacter>>,
+) {
+    if !keyboard.just_pressed(bindings.repair) {
+        return;
+    }
+    let Some((ship_tf, mut hull, mut stores)) = ship_query.iter_mut().next() else { return };
+    if !mode.can_work_ship(character_query.iter().next(), ship_tf) {
+        return;
+    }
+    if hull.integrity >= hull.max_integrity
+        || !(stores.take_repair_wood() || inventory.take(REPAIR_WOOD))
+    {
+        return;
+    }
+    hull.integrity = (hull.integrity + REPAIR_PER_PLANK).min(hull.max_integrity);
+    bevy::log::info!("Hull patched: {:.0}%", hull.integrity / hull.max_integrity * 100.0);
+}
+
+fn spawn_wood_pile(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let mesh = meshes.add(Cuboid::new(1.6, 0.12, 0.3));
+    let mat = materials.add(StandardMaterial {
+        base_color: Color::srgb(0.55, 0.38, 0.2),
+        perceptual_roughness: 0.9,
+        ..default()
+    });
+    for i in 0..WOOD_PILE_COUNT {
+        commands.spawn((
+            Mesh3d(mesh.clone()),
+            MeshMaterial3d(mat.clone()),
+            Transform::from_xyz(
+                SPAWN_ISLAND_X - 4.0 + i as f32 * 0.45,
+                0.3,
+                SPAWN_ISLAND_Z + 3.0,
+            ),
+            Interactable {
+                kind: InteractKind::Pickup {
+                    item_id: REPAIR_WOOD.into(),
+                },
+                range: VEHICLE_ENTER_RANGE,
+            },
+        ));
+    }
+}
+
+fn spawn_hull_ui(mut commands: Commands) {
+    let (root_id, text_id) = spawn_hud_row(&mut commands, SHIP_HULL_SLOT, HullUiText);
+    commands.insert_resource(HullUiRoot { root: root_id, text: text_id });
+}
+
+/// Shown at the helm, or on foot while the hull needs work.
+fn update_hull_ui(
+    mode: Res<PlayerMode>,
+    inventory: Res<Inventory>,
+    ui: Res<HullUiRoot>,
+    hull_query: Query<(&ShipHull, &HullVolume, &ShipStores)>,
+    mut visibility_query: Query<&mut Visibility>,
+    mut text_query: Query<&mut Text, With<HullUiText>>,
+) {
+    let Ok(mut root_vis) = visibility_query.get_mut(ui.root) else { return };
+    let Ok((hull, hull_volume, stores)) = hull_query.single() else {
+        *root_vis = Visibility::Hidden;
+        return;
+    };
+    let damaged = hull.integrity < hull.max_integrity || hull.flooded > 0.0;
+    if !(mode.in_boat || (!mode.in_vehicle() && damaged)) {
+        *root_vis = Visibility::Hidden;
+        return;
+    }
+    *root_vis = Visibility::Visible;
+    if let Ok(mut text) = text_query.get_mut(ui.text) {
+        let wood = stores.repair_wood as usize + inventory.count(REPAIR_WOOD);
+        *text = Text::new(format!(
+            "Hull {:.0}%  Flooded {:.0}%  [H] repair ({} wood)",
+            hull.integrity / hull.max_integrity * 100.0,
+            hull.flooded / hull_volume.volume().max(0.01) * 100.0,
+            wood,
+        ));
+    }
+}
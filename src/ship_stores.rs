@@ -0,0 +1,245 @@
+//! Ship stores – fuel for the engine, oxygen tanks and repair wood, and the cargo hold.
+//!
+//! Preparation phase of the extraction loop (proj.md): stock the ship at Safe Island (F)
+//! before the voyage. The engine (G to start) burns fuel; run dry and you sail home.
+//! Stores, inventory and winched-up heavy artifacts are all mass aboard, stowed where they
+//! sit in the boat, so a full afterdeck of loot trims her down by the stern.
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+use crate::artifacts::{Inventory, REPAIR_WOOD_MASS};
+use crate::character::MarineCharacter;
+use crate::game_state::GameState;
+use crate::hud::{spawn_hud_row, STORES_SLOT};
+use crate::player::PlayerMode;
+use crate::settings::InputBindings;
+use crate::ship::{Ship, ShipPropulsion, WATER_DENSITY};
+use crate::ship_hull::{flood_hull, ShipHull};
+use crate::world::{SPAWN_ISLAND_X, SPAWN_ISLAND_Z};
+
+/// Fuel tank capacity (L).
+const MAX_FUEL: f32 = 400.0;
+
+/// Fuel burned per second at full throttle (L/s). A full tank is about an hour under way.
+const FUEL_BURN_RATE: f32 = 0.1;
+
+/// Diesel density (kg/L).
+const FUEL_DENSITY: f32 = 0.85;
+
+/// Oxygen tank rack size.
+const MAX_OXYGEN_TANKS: u32 = 6;
+
+/// Mass (kg) of one charged oxygen tank.
+const OXYGEN_TANK_MASS: f32 = 15.0;
+
+/// Air in one tank, in bell oxygen units: a full diving bell.
+const OXYGEN_PER_TANK: f32 = 100.0;
+
+/// Planks the wood locker holds.
+const MAX_REPAIR_WOOD: u32 = 8;
+
+/// Ship within this distance (m) of Safe Island can take on stores.
+const HARBOUR_RADIUS: f32 = 60.0;
+
+/// Body-space stowage (m). Fuel tank low aft, tanks and wood racked forward either side,
+/// the hold amidships and heavy loot on the afterdeck by the winch.
+const FUEL_TANK: Vec3 = Vec3::new(0.0, -0.8, 6.0);
+const TANK_RACK: Vec3 = Vec3::new(2.5, 0.5, -4.0);
+const WOOD_LOCKER: Vec3 = Vec3::new(-2.5, 0.5, -4.0);
+const HOLD: Vec3 = Vec3::new(0.0, -0.6, 0.0);
+const AFTERDECK: Vec3 = Vec3::new(0.0, 1.75, 7.5);
+
+#[derive(Component)]
+pub struct ShipStores {
+    /// Fuel aboard (L).
+    pub fuel: f32,
+    pub max_fuel: f32,
+    pub oxygen_tanks: u32,
+    pub max_oxygen_tanks: u32,
+    pub repair_wood: u32,
+    pub max_repair_wood: u32,
+    /// Stores, cargo and floodwater (kg) added to the hull at the last step.
+    pub load: f32,
+    /// Air left in the tank on the manifold, once one has been opened.
+    open_tank: f32,
+}
+
+impl ShipStores {
+    /// Stores as a ship starts out: a little fuel, nothing else.
+    pub fn starting() -> Self {
+        Self {
+            fuel: MAX_FUEL * 0.25,
+            max_fuel: MAX_FUEL,
+            oxygen_tanks: 0,
+            max_oxygen_tanks: MAX_OXYGEN_TANKS,
+            repair_wood: 0,
+            max_repair_wood: MAX_REPAIR_WOOD,
+            load: 0.0,
+            open_tank: 0.0,
+        }
+    }
+
+    /// Starting stores plus the added mass Rapier carries for the load.
+    pub fn bundle() -> impl Bundle {
+        (Self::starting(), AdditionalMassProperties::Mass(0.0))
+    }
+
+    /// Burns fuel for `full_throttle_secs` seconds at full throttle.
+    pub fn burn(&mut self, full_throttle_secs: f32) {
+        self.fuel = (self.fuel - FUEL_BURN_RATE * full_throttle_secs).max(0.0);
+    }
+
+    /// Removes one plank from the locker. False if it was empty.
+    pub fn take_repair_wood(&mut self) -> bool {
+        if self.repair_wood == 0 {
+            return false;
+        }
+        self.repair_wood -= 1;
+        true
+    }
+
+    /// Draws up to `amount` of air, opening a fresh tank when the one on the manifold runs
+    /// dry. Returns what was drawn.
+    pub fn draw_oxygen(&mut self, amount: f32) -> f32 {
+        let mut drawn = 0.0;
+        while drawn < amount {
+            if self.open_tank <= 0.0 {
+                if self.oxygen_tanks == 0 {
+                    break;
+                }
+                self.oxygen_tanks -= 1;
+                self.open_tank = OXYGEN_PER_TANK;
+            }
+            let take = (amount - drawn).min(self.open_tank);
+            self.open_tank -= take;
+            drawn += take;
+        }
+        drawn
+    }
+
+    fn restock(&mut self) {
+        self.fuel = self.max_fuel;
+        self.oxygen_tanks = self.max_oxygen_tanks;
+        self.repair_wood = self.max_repair_wood;
+    }
+}
+
+#[derive(Component)]
+struct StoresUiText;
+
+#[derive(Resource)]
+struct StoresUiRoot(Entity);
+
+pub struct ShipStoresPlugin;
+
+impl Plugin for ShipStoresPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_stores_ui)
+            .add_systems(
+                FixedUpdate,
+                load_ship
+                    .after(flood_hull)
+                    .before(PhysicsSet::SyncBackend)
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                Update,
+                (resupply, update_stores_ui).run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+/// Sums everything aboard into one added mass at its combined centre.
+fn load_ship(
+    inventory: Res<Inventory>,
+    mut query: Query<(&ShipHull, &mut ShipStores, &mut AdditionalMassProperties)>,
+) {
+    let heavy = inventory.heavy_mass();
+    for (hull, mut stores, mut added_mass) in query.iter_mut() {
+        let loads = [
+            (stores.fuel * FUEL_DENSITY, FUEL_TANK),
+            (stores.oxygen_tanks as f32 * OXYGEN_TANK_MASS, TANK_RACK),
+            (stores.repair_wood as f32 * REPAIR_WOOD_MASS, WOOD_LOCKER),
+            (inventory.mass() - heavy, HOLD),
+            (heavy, AFTERDECK),
+            (hull.flooded * WATER_DENSITY, hull.flood_centre),
+        ];
+        let mass: f32 = loads.iter().map(|(kg, _)| kg).sum();
+        let moment: Vec3 = loads.iter().map(|(kg, at)| *at * *kg).sum();
+        stores.load = mass;
+        *added_mass = AdditionalMassProperties::MassProperties(MassProperties {
+            local_center_of_mass: if mass > 0.0 { moment / mass } else { Vec3::ZERO },
+            mass,
+            principal_inertia_local_frame: Quat::IDENTITY,
+            principal_inertia: Vec3::ZERO,
+        });
+    }
+}
+
+/// F tops up fuel, oxygen tanks and wood while the ship lies off Safe Island, from the
+/// helm or standing within reach of the boat.
+fn resupply(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<InputBindings>,
+    mode: Res<PlayerMode>,
+    character_query: Query<&Transform, With<MarineCharacter>>,
+    mut ship_query: Query<(&Transform, &mut ShipStores), Without<MarineCharacter>>,
+) {
+    if !keyboard.just_pressed(bindings.resupply) {
+        return;
+    }
+    let Some((ship_tf, mut stores)) = ship_query.iter_mut().next() else { return };
+    if !mode.can_work_ship(character_query.iter().next(), ship_tf) || !in_harbour(ship_tf.translation) {
+        return;
+    }
+    stores.restock();
+    bevy::log::info!("Ship stocked: fuel, oxygen tanks and repair wood full");
+}
+
+fn in_harbour(position: Vec3) -> bool {
+    Vec2::new(position.x - SPAWN_ISLAND_X, position.z - SPAWN_ISLAND_Z).length() <= HARBOUR_RADIUS
+}
+
+fn spawn_stores_ui(mut commands: Commands) {
+    let (root_id, _) = spawn_hud_row(&mut commands, STORES_SLOT, StoresUiText);
+    commands.insert_resource(StoresUiRoot(root_id));
+}
+
+fn update_stores_ui(
+    mode: Res<PlayerMode>,
+    ui: Res<StoresUiRoot>,
+    ship_query: Query<(&Transform, &Ship, &ShipStores)>,
+    mut visibility_query: Query<&mut Visibility>,
+    mut text_query: Query<&mut Text, With<StoresUiText>>,
+) {
+    let Ok(mut root_vis) = visibility_query.get_mut(ui.0) else { return };
+    let Ok((transform, ship, stores)) = ship_query.single() else {
+        *root_vis = Visibility::Hidden;
+        return;
+    };
+    if !mode.in_boat {
+        *root_vis = Visibility::Hidden;
+        return;
+    }
+    *root_vis = Visibility::Visible;
+    let Ok(mut text) = text_query.single_mut() else { return };
+    let engine = match ship.propulsion {
+        ShipPropulsion::Engine if stores.fuel <= 0.0 => "out of fuel",
+        ShipPropulsion::Engine => "running",
+        ShipPropulsion::Sail => "off",
+    };
+    let harbour = if in_harbour(transform.translation) { "  [F] resupply" } else { "" };
+    *text = Text::new(format!(
+        "Fuel {:.0}/{:.0} L  O2 tanks {}/{}  Wood {}/{}  Load {:.1} t  Engine {} [G]{}",
+        stores.fuel,
+        stores.max_fuel,
+        stores.oxygen_tanks,
+        stores.max_oxygen_tanks,
+        stores.repair_wood,
+        stores.max_repair_wood,
+        stores.load * 0.001,
+        engine,
+        harbour,
+    ));
+}
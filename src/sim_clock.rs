@@ -0,0 +1,60 @@
+//! Simulation clock – fixed-step time that only advances while Playing.
+//!
+//! Ocean phase, tide, weather, buoyancy, the sub and the boids all step on the fixed
+//! schedules from this clock, so the same inputs give the same ocean and ship states
+//! regardless of frame rate, pauses or time spent in the menu. Rapier steps with them.
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+use crate::game_state::GameState;
+
+/// Simulation steps per second.
+pub const SIM_HZ: f64 = 60.0;
+
+/// Length of one simulation step (s).
+pub const SIM_DT: f32 = (1.0 / SIM_HZ) as f32;
+
+/// Steps taken while Playing. Elapsed time derives from the tick count, so it never drifts.
+#[derive(Resource, Default)]
+pub struct SimClock {
+    pub tick: u64,
+}
+
+impl SimClock {
+    /// Simulated seconds since the session started.
+    pub fn elapsed_secs(&self) -> f32 {
+        (self.tick as f64 / SIM_HZ) as f32
+    }
+}
+
+pub struct SimClockPlugin;
+
+impl Plugin for SimClockPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Time::<Fixed>::from_hz(SIM_HZ))
+            .init_resource::<SimClock>()
+            .add_systems(
+                FixedFirst,
+                advance_sim_clock.run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(Update, pause_physics_outside_playing);
+    }
+}
+
+pub fn advance_sim_clock(mut clock: ResMut<SimClock>) {
+    clock.tick += 1;
+}
+
+/// Rapier would otherwise keep stepping in the menu and while paused.
+fn pause_physics_outside_playing(
+    state: Res<State<GameState>>,
+    mut config: Query<&mut RapierConfiguration>,
+) {
+    let active = *state.get() == GameState::Playing;
+    for mut config in config.iter_mut() {
+        if config.physics_pipeline_active != active {
+            config.physics_pipeline_active = active;
+        }
+    }
+}
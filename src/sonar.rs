@@ -0,0 +1,493 @@
+//! Sonar – the Scan in Sail, Scan, Dive, Extract.
+//!
+//! An active ping (N) from the ship or the sub sends out a wavefront. As it passes artifacts,
+//! wrecks and fish with a clear line back (raycast against fixed colliders), and the terrain
+//! struck by a fan of beams, each returns a blip on the HUD display, fading as it ages. The
+//! sub's pings cost battery. The ship also runs a passive depth sounder: a beam straight
+//! down, plotted as the seabed profile passing under the hull.
+
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use bevy::ui::PositionType;
+use bevy_rapier3d::prelude::*;
+
+use crate::artifacts::Artifact;
+use crate::diving_bell::Submersible;
+use crate::fauna::Boid;
+use crate::game_state::GameState;
+use crate::player::PlayerMode;
+use crate::settings::InputBindings;
+use crate::ship::Ship;
+use crate::sub_power::SubBattery;
+use crate::tide::Tide;
+use crate::wreck::Wreck;
+
+/// Wavefront speed (m/s). Slowed from sound in water so the sweep can be watched.
+const WAVE_SPEED: f32 = 100.0;
+
+/// Ping range (m) of the ship's hull transducer and the sub's smaller one.
+const SHIP_SONAR_RANGE: f32 = 300.0;
+const SUB_SONAR_RANGE: f32 = 150.0;
+
+/// Ship transducer, below the keel, body space (m).
+const SHIP_TRANSDUCER: Vec3 = Vec3::new(0.0, -2.0, 0.0);
+
+/// Battery charge per ping from the sub.
+const SUB_PING_DRAW: f32 = 2.0;
+
+/// Terrain beams around the compass, and their elevations (degrees, negative down).
+const TERRAIN_BEAMS: usize = 36;
+const TERRAIN_ELEVATIONS: [f32; 5] = [-75.0, -45.0, -20.0, -5.0, 0.0];
+
+/// A contact blocked by terrain closer than this short of it (m) gives no return.
+const OCCLUSION_SLACK: f32 = 3.0;
+
+/// Seconds a return stays on the display, fading out.
+const RETURN_FADE: f32 = 6.0;
+
+/// Blips the display can show at once.
+const MAX_BLIPS: usize = 128;
+
+/// Display radius (px).
+const DISPLAY_RADIUS: f32 = 100.0;
+
+/// Depth sounder: seconds between soundings, soundings shown, deepest plotted (m).
+const SOUNDER_INTERVAL: f32 = 0.5;
+const SOUNDER_SAMPLES: usize = 60;
+const SOUNDER_MAX_DEPTH: f32 = 120.0;
+
+/// Sounder plot size (px).
+const SOUNDER_WIDTH: f32 = 220.0;
+const SOUNDER_HEIGHT: f32 = 80.0;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum ContactKind {
+    Terrain,
+    Artifact,
+    Wreck,
+    Fauna,
+}
+
+impl ContactKind {
+    fn color(self) -> Color {
+        match self {
+            ContactKind::Terrain => Color::srgb(0.35, 0.75, 0.45),
+            ContactKind::Artifact => Color::srgb(1.0, 0.8, 0.2),
+            ContactKind::Wreck => Color::srgb(1.0, 0.45, 0.2),
+            ContactKind::Fauna => Color::srgb(0.4, 0.85, 1.0),
+        }
+    }
+}
+
+/// Which vessel's transducer pinged.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Transducer {
+    Ship,
+    Sub,
+}
+
+struct SonarReturn {
+    source: Transducer,
+    kind: ContactKind,
+    position: Vec3,
+    age: f32,
+}
+
+/// A wavefront on its way out.
+struct Ping {
+    source: Transducer,
+    origin: Vec3,
+    radius: f32,
+    range: f32,
+    /// Terrain beam hits (distance, point), nearest last, revealed as the front passes.
+    terrain: Vec<(f32, Vec3)>,
+}
+
+#[derive(Resource, Default)]
+struct Sonar {
+    ping: Option<Ping>,
+    returns: Vec<SonarReturn>,
+}
+
+/// Water depth (m) under the ship, oldest first.
+#[derive(Resource, Default)]
+struct DepthSounder {
+    samples: VecDeque<f32>,
+    timer: f32,
+}
+
+#[derive(Resource)]
+struct SonarUi {
+    root: Entity,
+    ring: Entity,
+    blips: Vec<Entity>,
+}
+
+#[derive(Resource)]
+struct SounderUi {
+    root: Entity,
+    bars: Vec<Entity>,
+    label: Entity,
+}
+
+pub struct SonarPlugin;
+
+impl Plugin for SonarPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Sonar>()
+            .init_resource::<DepthSounder>()
+            .add_systems(Startup, (spawn_sonar_ui, spawn_sounder_ui))
+            .add_systems(
+                Update,
+                (
+                    (ping, propagate_ping, fade_returns)
+                        .chain()
+                        .run_if(in_state(GameState::Playing)),
+                    sound_depth.run_if(in_state(GameState::Playing)),
+                    update_sonar_ui.run_if(in_state(GameState::Playing)),
+                    update_sounder_ui.run_if(in_state(GameState::Playing)),
+                ),
+            );
+    }
+}
+
+/// N from the helm of either vessel sends a ping, if none is still going out.
+fn ping(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<InputBindings>,
+    mode: Res<PlayerMode>,
+    rapier_context: ReadRapierContext,
+    mut sonar: ResMut<Sonar>,
+    ship_query: Query<&Transform, With<Ship>>,
+    mut sub_query: Query<(&Transform, &mut SubBattery), With<Submersible>>,
+) {
+    if !keyboard.just_pressed(bindings.sonar_ping) || !mode.in_vehicle() || sonar.ping.is_some() {
+        return;
+    }
+    let (source, origin, range) = if mode.in_submersible {
+        let Ok((sub_tf, mut battery)) = sub_query.single_mut() else { return };
+        if !battery.draw(SUB_PING_DRAW) {
+            bevy::log::warn!("Not enough battery to ping");
+            return;
+        }
+        (Transducer::Sub, sub_tf.translation, SUB_SONAR_RANGE)
+    } else {
+        let Ok(ship_tf) = ship_query.single() else { return };
+        (
+            Transducer::Ship,
+            ship_tf.translation + ship_tf.rotation * SHIP_TRANSDUCER,
+            SHIP_SONAR_RANGE,
+        )
+    };
+    let Ok(context) = rapier_context.single() else { return };
+    let mut terrain: Vec<(f32, Vec3)> = Vec::new();
+    for i in 0..TERRAIN_BEAMS {
+        let bearing = i as f32 / TERRAIN_BEAMS as f32 * std::f32::consts::TAU;
+        for elevation in TERRAIN_ELEVATIONS {
+            let dir = Quat::from_euler(EulerRot::YXZ, bearing, elevation.to_radians(), 0.0)
+                * Vec3::NEG_Z;
+            if let Some((_, toi)) = context.cast_ray(origin, dir, range, true, QueryFilter::only_fixed()) {
+                terrain.push((toi, origin + dir * toi));
+            }
+        }
+    }
+    terrain.sort_by(|a, b| b.0.total_cmp(&a.0));
+    sonar.returns.retain(|r| r.source != source);
+    sonar.ping = Some(Ping {
+        source,
+        origin,
+        radius: 0.0,
+        range,
+        terrain,
+    });
+}
+
+/// Pushes the wavefront out. Whatever it passes this frame with a clear line back returns.
+fn propagate_ping(
+    time: Res<Time>,
+    rapier_context: ReadRapierContext,
+    mut sonar: ResMut<Sonar>,
+    artifact_query: Query<(Entity, &GlobalTransform), With<Artifact>>,
+    wreck_query: Query<(Entity, &Transform), With<Wreck>>,
+    boid_query: Query<(Entity, &Transform), With<Boid>>,
+) {
+    let Ok(context) = rapier_context.single() else { return };
+    let Some(mut ping) = sonar.ping.take() else { return };
+    let passed = ping.radius;
+    ping.radius = (ping.radius + WAVE_SPEED * time.delta_secs()).min(ping.range);
+    let (origin, radius, source) = (ping.origin, ping.radius, ping.source);
+
+    let mut returns = Vec::new();
+    while ping.terrain.last().is_some_and(|(toi, _)| *toi <= radius) {
+        let (_, position) = ping.terrain.pop().unwrap();
+        returns.push((ContactKind::Terrain, position));
+    }
+    let contacts = artifact_query
+        .iter()
+        .map(|(entity, global)| (entity, global.translation(), ContactKind::Artifact))
+        .chain(wreck_query.iter().map(|(entity, t)| (entity, t.translation, ContactKind::Wreck)))
+        .chain(boid_query.iter().map(|(entity, t)| (entity, t.translation, ContactKind::Fauna)));
+    for (entity, position, kind) in contacts {
+        let to = position - origin;
+        let distance = to.length();
+        if distance <= passed || distance > radius || distance < f32::EPSILON {
+            continue;
+        }
+        let blocked = context
+            .cast_ray(origin, to / distance, distance, true, QueryFilter::only_fixed())
+            .is_some_and(|(hit, toi)| hit != entity && toi < distance - OCCLUSION_SLACK);
+        if !blocked {
+            returns.push((kind, position));
+        }
+    }
+
+    sonar.returns.extend(returns.into_iter().map(|(kind, position)| SonarReturn {
+        source,
+        kind,
+        position,
+        age: 0.0,
+    }));
+    if ping.radius < ping.range {
+        sonar.ping = Some(ping);
+    }
+}
+
+fn fade_returns(time: Res<Time>, mut sonar: ResMut<Sonar>) {
+    let dt = time.delta_secs();
+    for r in sonar.returns.iter_mut() {
+        r.age += dt;
+    }
+    sonar.returns.retain(|r| r.age < RETURN_FADE);
+}
+
+/// The ship's downward beam, every half second, whether anyone's watching or not.
+fn sound_depth(
+    time: Res<Time>,
+    tide: Res<Tide>,
+    rapier_context: ReadRapierContext,
+    mut sounder: ResMut<DepthSounder>,
+    ship_query: Query<&Transform, With<Ship>>,
+) {
+    sounder.timer += time.delta_secs();
+    if sounder.timer < SOUNDER_INTERVAL {
+        return;
+    }
+    sounder.timer = 0.0;
+    let Ok(ship_tf) = ship_query.single() else { return };
+    let Ok(context) = rapier_context.single() else { return };
+    let from = ship_tf.translation + ship_tf.rotation * SHIP_TRANSDUCER;
+    let depth = context
+        .cast_ray(from, Vec3::NEG_Y, SOUNDER_MAX_DEPTH, true, QueryFilter::only_fixed())
+        .map_or(SOUNDER_MAX_DEPTH, |(_, toi)| tide.level - (from.y - toi));
+    if sounder.samples.len() == SOUNDER_SAMPLES {
+        sounder.samples.pop_front();
+    }
+    sounder.samples.push_back(depth.max(0.0));
+}
+
+fn spawn_sonar_ui(mut commands: Commands) {
+    let ring_id = commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            BorderColor::all(Color::srgba(0.4, 1.0, 0.6, 0.6)),
+            BorderRadius::MAX,
+            Visibility::Hidden,
+        ))
+        .id();
+    let blips: Vec<Entity> = (0..MAX_BLIPS)
+        .map(|_| {
+            commands
+                .spawn((
+                    Node {
+                        position_type: PositionType::Absolute,
+                        width: Val::Px(4.0),
+                        height: Val::Px(4.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::NONE),
+                    BorderRadius::MAX,
+                ))
+                .id()
+        })
+        .collect();
+    let root_id = commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(20.0),
+                top: Val::Px(20.0),
+                width: Val::Px(DISPLAY_RADIUS * 2.0),
+                height: Val::Px(DISPLAY_RADIUS * 2.0),
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.02, 0.08, 0.06, 0.85)),
+            BorderColor::all(Color::srgba(0.4, 1.0, 0.6, 0.8)),
+            BorderRadius::MAX,
+            Visibility::Hidden,
+        ))
+        .add_child(ring_id)
+        .add_children(&blips)
+        .id();
+    commands.insert_resource(SonarUi {
+        root: root_id,
+        ring: ring_id,
+        blips,
+    });
+}
+
+/// Returns from the vessel the player is in, heading up, range to the rim.
+fn update_sonar_ui(
+    mode: Res<PlayerMode>,
+    sonar: Res<Sonar>,
+    ui: Res<SonarUi>,
+    ship_query: Query<&Transform, With<Ship>>,
+    sub_query: Query<&Transform, With<Submersible>>,
+    mut visibility_query: Query<&mut Visibility>,
+    mut node_query: Query<&mut Node>,
+    mut color_query: Query<&mut BackgroundColor>,
+) {
+    let (source, vessel, range) = if mode.in_submersible {
+        (Transducer::Sub, sub_query.single().ok(), SUB_SONAR_RANGE)
+    } else if mode.in_boat {
+        (Transducer::Ship, ship_query.single().ok(), SHIP_SONAR_RANGE)
+    } else {
+        (Transducer::Ship, None, SHIP_SONAR_RANGE)
+    };
+    let Ok(mut root_vis) = visibility_query.get_mut(ui.root) else { return };
+    let Some(vessel) = vessel else {
+        *root_vis = Visibility::Hidden;
+        return;
+    };
+    *root_vis = Visibility::Visible;
+    let scale = DISPLAY_RADIUS / range;
+    let to_display = |position: Vec3| {
+        let local = vessel.rotation.inverse() * (position - vessel.translation);
+        Vec2::new(local.x, local.z) * scale
+    };
+
+    let ping = sonar.ping.as_ref().filter(|p| p.source == source);
+    if let Ok(mut ring_vis) = visibility_query.get_mut(ui.ring) {
+        *ring_vis = if ping.is_some() { Visibility::Inherited } else { Visibility::Hidden };
+    }
+    if let (Some(ping), Ok(mut ring)) = (ping, node_query.get_mut(ui.ring)) {
+        let center = to_display(ping.origin);
+        let r = ping.radius * scale;
+        ring.left = Val::Px(DISPLAY_RADIUS + center.x - r);
+        ring.top = Val::Px(DISPLAY_RADIUS + center.y - r);
+        ring.width = Val::Px(r * 2.0);
+        ring.height = Val::Px(r * 2.0);
+    }
+
+    let mut shown = sonar
+        .returns
+        .iter()
+        .filter(|r| r.source == source)
+        .map(|r| (to_display(r.position), r))
+        .filter(|(p, _)| p.length() <= DISPLAY_RADIUS);
+    for &blip in ui.blips.iter() {
+        let Ok(mut color) = color_query.get_mut(blip) else { continue };
+        let Some((p, r)) = shown.next() else {
+            color.0 = Color::NONE;
+            continue;
+        };
+        color.0 = r.kind.color().with_alpha(1.0 - r.age / RETURN_FADE);
+        if let Ok(mut node) = node_query.get_mut(blip) {
+            node.left = Val::Px(DISPLAY_RADIUS + p.x - 2.0);
+            node.top = Val::Px(DISPLAY_RADIUS + p.y - 2.0);
+        }
+    }
+}
+
+fn spawn_sounder_ui(mut commands: Commands) {
+    let bar_width = SOUNDER_WIDTH / SOUNDER_SAMPLES as f32;
+    let bars: Vec<Entity> = (0..SOUNDER_SAMPLES)
+        .map(|i| {
+            commands
+                .spawn((
+                    Node {
+                        position_type: PositionType::Absolute,
+                        left: Val::Px(i as f32 * bar_width),
+                        width: Val::Px(bar_width),
+                        top: Val::Px(SOUNDER_HEIGHT),
+                        bottom: Val::Px(0.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.55, 0.45, 0.3, 0.9)),
+                ))
+                .id()
+        })
+        .collect();
+    let label_id = commands
+        .spawn((
+            Text::new(""),
+            TextFont { font_size: 14.0, ..default() },
+            TextColor(Color::srgba(1.0, 1.0, 1.0, 0.95)),
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(6.0),
+                top: Val::Px(4.0),
+                ..default()
+            },
+        ))
+        .id();
+    let root_id = commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(20.0),
+                top: Val::Px(DISPLAY_RADIUS * 2.0 + 30.0),
+                width: Val::Px(SOUNDER_WIDTH),
+                height: Val::Px(SOUNDER_HEIGHT),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.05, 0.15, 0.3, 0.85)),
+            Visibility::Hidden,
+        ))
+        .add_children(&bars)
+        .add_child(label_id)
+        .id();
+    commands.insert_resource(SounderUi {
+        root: root_id,
+        bars,
+        label: label_id,
+    });
+}
+
+/// Seabed profile under the ship, newest on the right. At the helm only.
+fn update_sounder_ui(
+    mode: Res<PlayerMode>,
+    sounder: Res<DepthSounder>,
+    ui: Res<SounderUi>,
+    mut visibility_query: Query<&mut Visibility>,
+    mut node_query: Query<&mut Node>,
+    mut text_query: Query<&mut Text>,
+) {
+    let Ok(mut root_vis) = visibility_query.get_mut(ui.root) else { return };
+    if !mode.in_boat {
+        *root_vis = Visibility::Hidden;
+        return;
+    }
+    *root_vis = Visibility::Visible;
+    let offset = SOUNDER_SAMPLES - sounder.samples.len();
+    for (i, &bar) in ui.bars.iter().enumerate() {
+        let Ok(mut node) = node_query.get_mut(bar) else { continue };
+        let depth = i
+            .checked_sub(offset)
+            .and_then(|j| sounder.samples.get(j).copied())
+            .unwrap_or(SOUNDER_MAX_DEPTH);
+        node.top = Val::Px((depth / SOUNDER_MAX_DEPTH).min(1.0) * SOUNDER_HEIGHT);
+    }
+    if let Ok(mut text) = text_query.get_mut(ui.label) {
+        *text = match sounder.samples.back() {
+            Some(&depth) if depth < SOUNDER_MAX_DEPTH => Text::new(format!("Depth {:.0} m", depth)),
+            Some(_) => Text::new(format!("Depth > {:.0} m", SOUNDER_MAX_DEPTH)),
+            None => Text::new(""),
+        };
+    }
+}
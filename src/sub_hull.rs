@@ -0,0 +1,224 @@
+//! Submersible pressure hull – rated depth per tier, crush damage, impacts and implosion.
+//!
+//! Below its rated depth the hull takes stress damage, faster the deeper it goes; hitting
+//! terrain or a wreck hard (Rapier contact force events) dents it too. A damaged hull leaks,
+//! draining the bell's oxygen faster. At zero integrity it implodes: the pilot is thrown
+//! clear, the winch cable parts, and a replacement sub waits off Safe Island.
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+use crate::character::MarineCharacter;
+use crate::ballast::Ballast;
+use crate::diving_bell::{sub_spawn_transform, DivingBell};
+use crate::game_state::GameState;
+use crate::hud::{spawn_hud_row, SUB_HULL_SLOT};
+use crate::player::{put_camera_on_character, PlayerCamera, PlayerMode};
+use crate::ship_hull::{collision_damage, ContactDamage};
+use crate::sim_clock::SIM_DT;
+use crate::sub_power::SubBattery;
+use crate::tide::Tide;
+use crate::winch::{cut_cable, WinchState};
+
+/// Full hull integrity.
+const MAX_INTEGRITY: f32 = 100.0;
+
+/// Integrity lost per second per metre below the rated depth.
+const CRUSH_DAMAGE: f32 = 0.25;
+
+/// Contact force (N) the hull shrugs off: settling on the seabed, nudging a rock.
+/// Roughly the sub's own weight.
+const COLLISION_THRESHOLD: f32 = 2.0e5;
+
+/// Integrity lost per N·s of contact impulse above the threshold.
+const COLLISION_DAMAGE: f32 = 2.5e-4;
+
+/// Pressure hull class. Deeper-rated hulls open up the abyss.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SubTier {
+    Coastal,
+    Deep,
+    Abyssal,
+}
+
+impl SubTier {
+    /// Depth (m below the surface) the hull is rated for.
+    pub fn rated_depth(self) -> f32 {
+        match self {
+            SubTier::Coastal => 50.0,
+            SubTier::Deep => 120.0,
+            SubTier::Abyssal => 400.0,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct SubHull {
+    pub tier: SubTier,
+    pub integrity: f32,
+    pub max_integrity: f32,
+    /// Depth (m) at the last step.
+    pub depth: f32,
+}
+
+impl SubHull {
+    pub fn new(tier: SubTier) -> Self {
+        Self {
+            tier,
+            integrity: MAX_INTEGRITY,
+            max_integrity: MAX_INTEGRITY,
+            depth: 0.0,
+        }
+    }
+
+    /// Hull state plus the Rapier contact events it needs on the sub body.
+    pub fn bundle(tier: SubTier) -> impl Bundle {
+        (Self::new(tier), Self::contact_events())
+    }
+
+    pub fn damage(&mut self, amount: f32) {
+        self.integrity = (self.integrity - amount).max(0.0);
+    }
+
+    /// 0 = intact, 1 = imploding.
+    pub fn damage_fraction(&self) -> f32 {
+        1.0 - self.integrity / self.max_integrity
+    }
+}
+
+impl ContactDamage for SubHull {
+    const COLLISION_THRESHOLD: f32 = COLLISION_THRESHOLD;
+    const COLLISION_DAMAGE: f32 = COLLISION_DAMAGE;
+
+    fn take_damage(&mut self, amount: f32) {
+        self.damage(amount);
+    }
+}
+
+#[derive(Component)]
+struct SubHullUiText;
+
+#[derive(Resource)]
+struct SubHullUiRoot(Entity);
+
+pub struct SubHullPlugin;
+
+impl Plugin for SubHullPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_sub_hull_ui)
+            .add_systems(
+                FixedUpdate,
+                (
+                    collision_damage::<SubHull>,
+                    crush_depth.before(PhysicsSet::SyncBackend),
+                )
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                Update,
+                (implode, update_sub_hull_ui).run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+/// Past the rated depth, water pressure works the hull harder with every metre.
+fn crush_depth(tide: Res<Tide>, mut query: Query<(&Transform, &mut SubHull)>) {
+    for (transform, mut hull) in query.iter_mut() {
+        hull.depth = (tide.level - transform.translation.y).max(0.0);
+        let over = hull.depth - hull.tier.rated_depth();
+        if over > 0.0 {
+            hull.damage(over * CRUSH_DAMAGE * SIM_DT);
+        }
+    }
+}
+
+/// A hull at zero integrity gives way. The pilot is thrown clear into the water and the
+/// cable parts; the sub is replaced off Safe Island.
+fn implode(
+    mut commands: Commands,
+    mut mode: ResMut<PlayerMode>,
+    mut winch: ResMut<WinchState>,
+    mut sub_query: Query<(
+        Entity,
+        &mut Transform,
+        &mut Velocity,
+        &mut SubHull,
+        &mut DivingBell,
+        &mut SubBattery,
+        &mut Ballast,
+    )>,
+    camera_query: Query<Entity, With<PlayerCamera>>,
+    character_query: Query<Entity, With<MarineCharacter>>,
+) {
+    for (sub_id, mut transform, mut velocity, mut hull, mut bell, mut battery, mut ballast) in
+        sub_query.iter_mut()
+    {
+        if hull.integrity > 0.0 {
+            continue;
+        }
+        bevy::log::warn!(
+            "The sub's hull imploded at {:.0} m. A replacement waits off Safe Island.",
+            hull.depth
+        );
+        if mode.in_submersible {
+            mode.in_submersible = false;
+            if let (Some(cam_id), Some(char_id)) =
+                (camera_query.iter().next(), character_query.iter().next())
+            {
+                commands
+                    .entity(char_id)
+                    .insert(Transform::from_translation(transform.translation));
+                put_camera_on_character(&mut commands, cam_id, char_id);
+            }
+        }
+        if winch.tethered {
+            cut_cable(&mut commands, &mut winch, sub_id);
+        }
+
+        *transform = sub_spawn_transform();
+        *velocity = Velocity::zero();
+        *hull = SubHull::new(hull.tier);
+        bell.current_oxygen = bell.max_oxygen;
+        *battery = SubBattery::default();
+        *ballast = Ballast::default();
+    }
+}
+
+fn spawn_sub_hull_ui(mut commands: Commands) {
+    let (root_id, _) = spawn_hud_row(&mut commands, SUB_HULL_SLOT, SubHullUiText);
+    commands.insert_resource(SubHullUiRoot(root_id));
+}
+
+/// Depth against the rating, turning red past it.
+fn update_sub_hull_ui(
+    mode: Res<PlayerMode>,
+    ui: Res<SubHullUiRoot>,
+    hull_query: Query<&SubHull>,
+    mut visibility_query: Query<&mut Visibility>,
+    mut text_query: Query<(&mut Text, &mut TextColor), With<SubHullUiText>>,
+) {
+    let Ok(mut root_vis) = visibility_query.get_mut(ui.0) else { return };
+    let Ok(hull) = hull_query.single() else {
+        *root_vis = Visibility::Hidden;
+        return;
+    };
+    if !mode.in_submersible {
+        *root_vis = Visibility::Hidden;
+        return;
+    }
+    *root_vis = Visibility::Visible;
+    let Ok((mut text, mut color)) = text_query.single_mut() else { return };
+    let rated = hull.tier.rated_depth();
+    *text = Text::new(format!(
+        "Hull {:.0}%  Depth {:.0} m / rated {:.0} m ({:?})",
+        hull.integrity / hull.max_integrity * 100.0,
+        hull.depth,
+        rated,
+        hull.tier,
+    ));
+    color.0 = if hull.depth > rated {
+        Color::srgba(1.0, 0.35, 0.3, 0.95)
+    } else {
+        Color::srgba(1.0, 1.0, 1.0, 0.95)
+    };
+}
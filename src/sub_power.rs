@@ -0,0 +1,211 @@
+//! Submersible battery – the power budget for thrusters, floodlight and sonar.
+//!
+//! Thrust and ascent draw with the pilot's input, the floodlight (L) draws while it's on and
+//! sonar pings take their share. The winch cable trickles charge down while tethered, and the
+//! generator charges faster surfaced. Flat, the thrusters die and the light goes out, so
+//! running dark in the abyss buys time.
+
+use bevy::prelude::*;
+use bevy::ui::{AlignItems, FlexDirection, JustifyContent};
+use bevy_rapier3d::prelude::*;
+
+use crate::diving_bell::{is_surfaced, Submersible};
+use crate::game_state::GameState;
+use crate::ocean::OceanSolver;
+use crate::player::PlayerMode;
+use crate::settings::InputBindings;
+use crate::sim_clock::SIM_DT;
+use crate::winch::WinchState;
+
+/// Full charge.
+const BATTERY_CAPACITY: f32 = 100.0;
+
+/// Charge per second at full throttle.
+const THRUST_DRAW: f32 = 0.5;
+
+/// Charge per second ascending or descending flat out.
+const VERTICAL_DRAW: f32 = 0.3;
+
+/// Charge per second with the floodlight on.
+const FLOODLIGHT_DRAW: f32 = 0.2;
+
+/// Charge per second carried down the winch cable. A trickle: it stretches a dive on the
+/// tether but can't keep up with the thrusters.
+const CABLE_CHARGE_RATE: f32 = 0.2;
+
+/// Charge per second from the generator, surfaced.
+const SURFACE_CHARGE_RATE: f32 = 2.0;
+
+#[derive(Component)]
+pub struct SubBattery {
+    pub charge: f32,
+    pub capacity: f32,
+    pub floodlight: bool,
+}
+
+impl Default for SubBattery {
+    fn default() -> Self {
+        Self {
+            charge: BATTERY_CAPACITY,
+            capacity: BATTERY_CAPACITY,
+            floodlight: true,
+        }
+    }
+}
+
+impl SubBattery {
+    pub fn is_flat(&self) -> bool {
+        self.charge <= 0.0
+    }
+
+    /// Takes `amount` of charge if there's that much left. False (and nothing taken) if not.
+    pub fn draw(&mut self, amount: f32) -> bool {
+        if self.charge < amount {
+            return false;
+        }
+        self.charge -= amount;
+        true
+    }
+}
+
+/// The sub's floodlight, switched by the battery.
+#[derive(Component)]
+pub struct SubFloodlight;
+
+/// Battery bar fill node, next to the oxygen bar.
+#[derive(Component)]
+struct BatteryBarFill;
+
+#[derive(Resource)]
+struct BatteryUiRoot {
+    root: Entity,
+    fill: Entity,
+}
+
+pub struct SubPowerPlugin;
+
+impl Plugin for SubPowerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_battery_ui)
+            .add_systems(
+                FixedUpdate,
+                battery_budget
+                    .before(PhysicsSet::SyncBackend)
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                Update,
+                (
+                    toggle_floodlight
+                        .run_if(in_state(GameState::Playing))
+                        .run_if(|mode: Res<PlayerMode>| mode.in_submersible),
+                    switch_floodlight.run_if(in_state(GameState::Playing)),
+                    update_battery_ui.run_if(in_state(GameState::Playing)),
+                ),
+            );
+    }
+}
+
+/// Draws for the thrusters and light, charges down the cable and at the surface.
+pub(crate) fn battery_budget(
+    ocean: Res<OceanSolver>,
+    winch: Res<WinchState>,
+    mut query: Query<(&Transform, &Submersible, &mut SubBattery)>,
+) {
+    for (transform, sub, mut battery) in query.iter_mut() {
+        let mut draw = THRUST_DRAW * sub.current_throttle.abs()
+            + THRUST_DRAW * sub.current_steering.abs() * 0.5
+            + VERTICAL_DRAW * sub.current_vertical.abs();
+        if battery.floodlight {
+            draw += FLOODLIGHT_DRAW;
+        }
+        let mut charge = 0.0;
+        if winch.tethered {
+            charge += CABLE_CHARGE_RATE;
+        }
+        if is_surfaced(&ocean, transform.translation) {
+            charge += SURFACE_CHARGE_RATE;
+        }
+        battery.charge = (battery.charge + (charge - draw) * SIM_DT).clamp(0.0, battery.capacity);
+    }
+}
+
+fn toggle_floodlight(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<InputBindings>,
+    mut query: Query<&mut SubBattery>,
+) {
+    if !keyboard.just_pressed(bindings.floodlight) {
+        return;
+    }
+    for mut battery in query.iter_mut() {
+        battery.floodlight = !battery.floodlight;
+        bevy::log::info!("Floodlight {}", if battery.floodlight { "on" } else { "off" });
+    }
+}
+
+/// Light follows the switch, and goes out with the battery.
+fn switch_floodlight(
+    battery_query: Query<&SubBattery>,
+    mut light_query: Query<&mut Visibility, With<SubFloodlight>>,
+) {
+    let Ok(battery) = battery_query.single() else { return };
+    let lit = battery.floodlight && !battery.is_flat();
+    for mut visibility in light_query.iter_mut() {
+        visibility.set_if_neq(if lit { Visibility::Inherited } else { Visibility::Hidden });
+    }
+}
+
+fn spawn_battery_ui(mut commands: Commands) {
+    let fill_id = commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.95, 0.8, 0.2, 0.9)),
+            BatteryBarFill,
+        ))
+        .id();
+    let root_id = commands
+        .spawn((
+            Node {
+                width: Val::Px(120.0),
+                height: Val::Px(24.0),
+                position_type: bevy::ui::PositionType::Absolute,
+                left: Val::Px(230.0),
+                bottom: Val::Px(20.0),
+                flex_direction: FlexDirection::Row,
+                justify_content: JustifyContent::FlexStart,
+                align_items: AlignItems::Center,
+                padding: UiRect::all(Val::Px(2.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.1, 0.15, 0.25, 0.9)),
+            Visibility::Hidden,
+        ))
+        .add_child(fill_id)
+        .id();
+    commands.insert_resource(BatteryUiRoot { root: root_id, fill: fill_id });
+}
+
+fn update_battery_ui(
+    mode: Res<PlayerMode>,
+    ui: Res<BatteryUiRoot>,
+    battery_query: Query<&SubBattery>,
+    mut visibility_query: Query<&mut Visibility>,
+    mut fill_query: Query<&mut Node, With<BatteryBarFill>>,
+) {
+    let Ok(mut root_vis) = visibility_query.get_mut(ui.root) else { return };
+    if !mode.in_submersible {
+        *root_vis = Visibility::Hidden;
+        return;
+    }
+    *root_vis = Visibility::Visible;
+    let Ok(battery) = battery_query.single() else { return };
+    let pct = (battery.charge / battery.capacity).clamp(0.0, 1.0);
+    if let Ok(mut fill_node) = fill_query.get_mut(ui.fill) {
+        fill_node.width = Val::Percent(pct * 100.0);
+    }
+}
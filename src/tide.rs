@@ -0,0 +1,67 @@
+//! Tides – mean water level rises and falls twice per game day.
+//!
+//! Low water exposes reef tops and shallow wrecks; high water floods them.
+//! `Tide::level` is the live sea level – read it instead of `ocean::MEAN_SEA_LEVEL`.
+
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+
+use crate::game_state::GameState;
+use crate::ocean::{OceanSolver, MEAN_SEA_LEVEL};
+use crate::sim_clock::SIM_DT;
+
+/// Length of one game day (s). Two high and two low waters per day (semi-diurnal).
+pub const DAY_LENGTH_SECS: f32 = 1200.0;
+
+/// Half the tidal range (m): high water is MEAN_SEA_LEVEL + this, low water minus this.
+const TIDE_AMPLITUDE: f32 = 1.5;
+
+/// Tides per game day.
+const TIDES_PER_DAY: f32 = 2.0;
+
+#[derive(Resource)]
+pub struct Tide {
+    /// Fraction of the game day elapsed, 0..1. Persisted by save_load.
+    pub phase: f32,
+    /// Live mean water level (Y). Waves oscillate around this.
+    pub level: f32,
+}
+
+impl Default for Tide {
+    fn default() -> Self {
+        let mut tide = Self { phase: 0.0, level: MEAN_SEA_LEVEL };
+        tide.level = tide.level_at(tide.phase);
+        tide
+    }
+}
+
+impl Tide {
+    /// Water level (Y) at a day phase. Phase 0 is high water.
+    pub fn level_at(&self, phase: f32) -> f32 {
+        MEAN_SEA_LEVEL + TIDE_AMPLITUDE * (TAU * TIDES_PER_DAY * phase).cos()
+    }
+
+    pub fn set_phase(&mut self, phase: f32) {
+        self.phase = phase.rem_euclid(1.0);
+        self.level = self.level_at(self.phase);
+    }
+}
+
+pub struct TidePlugin;
+
+impl Plugin for TidePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Tide::default())
+            .add_systems(
+                FixedPreUpdate,
+                update_tide.run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+fn update_tide(mut tide: ResMut<Tide>, mut ocean: ResMut<OceanSolver>) {
+    let phase = tide.phase + SIM_DT / DAY_LENGTH_SECS;
+    tide.set_phase(phase);
+    ocean.sea_level = tide.level;
+}
@@ -0,0 +1,350 @@
+//! Wake and ripples – a dynamic heightfield layered on the Gerstner swell.
+//!
+//! Anything with a `WakeSource` that crosses the surface pushes water aside; the
+//! disturbance spreads as a damped 2D wave. The field lives in `OceanSolver`, so height
+//! queries and the water mesh see it: a boat following another rides its wake.
+//! The ship also throws bow spray when making way.
+
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::Velocity;
+
+use crate::game_state::GameState;
+use crate::marine_snow::FastNoise;
+use crate::ocean::OceanSolver;
+use crate::ship::{Ship, HULL_HALF_EXTENTS, SHIP_SCALE};
+use crate::sim_clock::SIM_DT;
+
+/// Ripple grid cells per side. The grid follows the ship.
+pub const RIPPLE_GRID: usize = 256;
+
+/// Ripple cell size (m). 256 cells cover ~190 m around the ship.
+pub const RIPPLE_CELL: f32 = 0.75;
+
+/// Ripple propagation speed (m/s). Roughly deep-water phase speed of 2–3 m waves.
+const RIPPLE_SPEED: f32 = 3.0;
+
+/// Fraction of ripple height kept after one second.
+const RIPPLE_RETAIN: f32 = 0.55;
+
+/// Fraction of foam kept after one second.
+const FOAM_RETAIN: f32 = 0.7;
+
+/// Ripple heights are clamped to this (m) so a violent splash can't blow up the solver.
+const RIPPLE_MAX_HEIGHT: f32 = 1.5;
+
+/// Bow spray particles in the pool.
+const SPRAY_COUNT: usize = 160;
+
+/// Ship speed (m/s) above which the bow throws spray.
+const SPRAY_MIN_SPEED: f32 = 2.0;
+
+/// Spray particles emitted per second per m/s above `SPRAY_MIN_SPEED`.
+const SPRAY_RATE: f32 = 12.0;
+
+/// Spray lifetime (s).
+const SPRAY_LIFETIME: f32 = 1.2;
+
+/// Bow position relative to the ship origin, in world metres (hull half-length times
+/// `SHIP_SCALE`). Forward is -Z.
+const BOW_OFFSET: Vec3 = Vec3::new(0.0, 0.0, -HULL_HALF_EXTENTS.z * SHIP_SCALE);
+
+/// Ripple layer at one point. Heights are relative to the Gerstner surface.
+#[derive(Clone, Copy, Default)]
+pub struct RippleSample {
+    pub height: f32,
+    /// Height gradient (dh/dx, dh/dz).
+    pub slope: Vec2,
+    /// Vertical water velocity (m/s).
+    pub velocity: f32,
+    /// Foam coverage 0..1.
+    pub foam: f32,
+}
+
+/// Square heightfield in world XZ. Stepped with the simulation clock.
+pub struct RippleField {
+    /// World cell coordinate of grid cell (0, 0).
+    corner: IVec2,
+    height: Vec<f32>,
+    previous: Vec<f32>,
+    foam: Vec<f32>,
+}
+
+impl Default for RippleField {
+    fn default() -> Self {
+        let cells = RIPPLE_GRID * RIPPLE_GRID;
+        Self {
+            corner: IVec2::splat(-(RIPPLE_GRID as i32) / 2),
+            height: vec![0.0; cells],
+            previous: vec![0.0; cells],
+            foam: vec![0.0; cells],
+        }
+    }
+}
+
+impl RippleField {
+    /// Scrolls the grid so `center` sits in its middle. Cells scrolled in start still.
+    pub fn recenter(&mut self, center: Vec2) {
+        let corner = (center / RIPPLE_CELL).floor().as_ivec2() - IVec2::splat(RIPPLE_GRID as i32 / 2);
+        let shift = corner - self.corner;
+        if shift == IVec2::ZERO {
+            return;
+        }
+        let n = RIPPLE_GRID as i32;
+        for layer in [&mut self.height, &mut self.previous, &mut self.foam] {
+            let old = std::mem::replace(layer, vec![0.0; RIPPLE_GRID * RIPPLE_GRID]);
+            for z in 0..n {
+                for x in 0..n {
+                    let (ox, oz) = (x + shift.x, z + shift.y);
+                    if (0..n).contains(&ox) && (0..n).contains(&oz) {
+                        layer[(z * n + x) as usize] = old[(oz * n + ox) as usize];
+                    }
+                }
+            }
+        }
+        self.corner = corner;
+    }
+
+    /// Advances the damped wave equation by `dt`. Edges are held flat.
+    pub fn step(&mut self, dt: f32) {
+        let n = RIPPLE_GRID;
+        let courant = RIPPLE_SPEED * dt / RIPPLE_CELL;
+        let k = courant * courant;
+        let retain = RIPPLE_RETAIN.powf(dt);
+        let foam_retain = FOAM_RETAIN.powf(dt);
+        // Verlet: the previous buffer becomes the next one in place.
+        for z in 1..n - 1 {
+            for x in 1..n - 1 {
+                let i = z * n + x;
+                let h = self.height[i];
+                let laplacian = self.height[i - 1] + self.height[i + 1] + self.height[i - n]
+                    + self.height[i + n]
+                    - 4.0 * h;
+                let next = (2.0 * h - self.previous[i] + k * laplacian) * retain;
+                self.previous[i] = next.clamp(-RIPPLE_MAX_HEIGHT, RIPPLE_MAX_HEIGHT);
+            }
+        }
+        for j in 0..n {
+            for i in [j, (n - 1) * n + j, j * n, j * n + n - 1] {
+                self.previous[i] = 0.0;
+            }
+        }
+        std::mem::swap(&mut self.height, &mut self.previous);
+        for foam in self.foam.iter_mut() {
+            *foam *= foam_retain;
+        }
+    }
+
+    /// Lowers the water by `depth` (m) over a disc at `center`, with a cosine falloff,
+    /// and churns `foam` into it. Negative depth raises it.
+    pub fn disturb(&mut self, center: Vec2, radius: f32, depth: f32, foam: f32) {
+        let n = RIPPLE_GRID as i32;
+        let reach = (radius / RIPPLE_CELL).ceil() as i32;
+        let cell = (center / RIPPLE_CELL).floor().as_ivec2() - self.corner;
+        for z in (cell.y - reach).max(1)..=(cell.y + reach).min(n - 2) {
+            for x in (cell.x - reach).max(1)..=(cell.x + reach).min(n - 2) {
+                let world = (IVec2::new(x, z) + self.corner).as_vec2() * RIPPLE_CELL;
+                let d = world.distance(center);
+                if d >= radius {
+                    continue;
+                }
+                let w = 0.5 * (1.0 + (PI * d / radius).cos());
+                let i = (z * n + x) as usize;
+                self.height[i] = (self.height[i] - depth * w).clamp(-RIPPLE_MAX_HEIGHT, RIPPLE_MAX_HEIGHT);
+                self.foam[i] = (self.foam[i] + foam * w).min(1.0);
+            }
+        }
+    }
+
+    /// Bilinear sample at world XZ. Zero outside the grid.
+    pub fn sample(&self, xz: Vec2) -> RippleSample {
+        let n = RIPPLE_GRID;
+        let grid = xz / RIPPLE_CELL - self.corner.as_vec2();
+        let base = grid.floor();
+        if base.x < 0.0 || base.y < 0.0 || base.x >= (n - 1) as f32 || base.y >= (n - 1) as f32 {
+            return RippleSample::default();
+        }
+        let f = grid - base;
+        let i = base.y as usize * n + base.x as usize;
+        let corners = |layer: &[f32]| (layer[i], layer[i + 1], layer[i + n], layer[i + n + 1]);
+        let lerp2 = |(a, b, c, d): (f32, f32, f32, f32)| {
+            let top = a + (b - a) * f.x;
+            let bottom = c + (d - c) * f.x;
+            top + (bottom - top) * f.y
+        };
+
+        let (h00, h10, h01, h11) = corners(&self.height);
+        let slope = Vec2::new(
+            (h10 - h00) + ((h11 - h01) - (h10 - h00)) * f.y,
+            (h01 - h00) + ((h11 - h10) - (h01 - h00)) * f.x,
+        ) / RIPPLE_CELL;
+        let height = lerp2((h00, h10, h01, h11));
+        let previous = lerp2(corners(&self.previous));
+        RippleSample {
+            height,
+            slope,
+            velocity: (height - previous) / SIM_DT,
+            foam: lerp2(corners(&self.foam)),
+        }
+    }
+}
+
+/// Body that stirs the ripple layer while it crosses the surface.
+#[derive(Component)]
+pub struct WakeSource {
+    /// Footprint radius (m); also how far above/below the surface it still disturbs.
+    pub radius: f32,
+    /// Metres of water displaced per metre travelled through the surface.
+    pub strength: f32,
+    /// Position at the previous step, for the velocity estimate.
+    pub last_position: Option<Vec3>,
+}
+
+impl WakeSource {
+    pub fn new(radius: f32, strength: f32) -> Self {
+        Self {
+            radius,
+            strength,
+            last_position: None,
+        }
+    }
+}
+
+#[derive(Component)]
+struct SprayParticle {
+    velocity: Vec3,
+    /// Seconds left; hidden when zero or below.
+    life: f32,
+}
+
+/// Fractional spray particles owed from previous frames.
+#[derive(Resource, Default)]
+struct SprayBacklog(f32);
+
+pub struct WakePlugin;
+
+impl Plugin for WakePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SprayBacklog>()
+            .add_systems(Startup, spawn_spray_pool)
+            .add_systems(
+                FixedPreUpdate,
+                (step_ripples, stir_ripples)
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(Update, update_bow_spray.run_if(in_state(GameState::Playing)));
+    }
+}
+
+/// Keeps the grid on the ship (the shared focus, so peers agree) and advances it.
+fn step_ripples(mut ocean: ResMut<OceanSolver>, ship_query: Query<&Transform, With<Ship>>) {
+    if let Some(ship) = ship_query.iter().next() {
+        ocean.ripples.recenter(Vec2::new(ship.translation.x, ship.translation.z));
+    }
+    ocean.ripples.step(SIM_DT);
+}
+
+fn stir_ripples(
+    mut ocean: ResMut<OceanSolver>,
+    mut sources: Query<(&GlobalTransform, &mut WakeSource)>,
+) {
+    for (global, mut source) in sources.iter_mut() {
+        let pos = global.translation();
+        let Some(last) = source.last_position.replace(pos) else { continue };
+        let surface = ocean.surface_at(pos).position.y;
+        let immersion = 1.0 - (pos.y - surface).abs() / source.radius.max(0.01);
+        if immersion <= 0.0 {
+            continue;
+        }
+        let moved = pos - last;
+        let travel = Vec2::new(moved.x, moved.z).length() + moved.y.abs();
+        if travel <= 0.0 {
+            continue;
+        }
+        let depth = source.strength * travel * immersion;
+        let foam = (travel / SIM_DT) * immersion * 0.05;
+        ocean
+            .ripples
+            .disturb(Vec2::new(pos.x, pos.z), source.radius, depth, foam);
+    }
+}
+
+fn spawn_spray_pool(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let mesh = meshes.add(Sphere::new(1.0).mesh().uv(6, 4));
+    let material = materials.add(StandardMaterial {
+        base_color: Color::srgba(1.0, 1.0, 1.0, 0.7),
+        perceptual_roughness: 0.6,
+        alpha_mode: AlphaMode::Blend,
+        ..default()
+    });
+    for _ in 0..SPRAY_COUNT {
+        commands.spawn((
+            Mesh3d(mesh.clone()),
+            MeshMaterial3d(material.clone()),
+            Transform::default(),
+            Visibility::Hidden,
+            SprayParticle { velocity: Vec3::ZERO, life: 0.0 },
+        ));
+    }
+}
+
+/// Throws droplets off both bow quarters in proportion to speed; they fall back and vanish.
+fn update_bow_spray(
+    time: Res<Time>,
+    ocean: Res<OceanSolver>,
+    mut backlog: ResMut<SprayBacklog>,
+    mut rng: Local<FastNoise>,
+    ship_query: Query<(&Transform, &Velocity), With<Ship>>,
+    mut particles: Query<(&mut Transform, &mut Visibility, &mut SprayParticle), Without<Ship>>,
+) {
+    let dt = time.delta_secs();
+
+    let mut emit = 0usize;
+    let mut bow = None;
+    if let Some((ship_tf, ship_vel)) = ship_query.iter().next() {
+        let speed = Vec2::new(ship_vel.linvel.x, ship_vel.linvel.z).length();
+        let bow_pos = ship_tf.translation + ship_tf.rotation * BOW_OFFSET;
+        if speed > SPRAY_MIN_SPEED && bow_pos.y < ocean.wave_height_at(bow_pos) + 0.3 {
+            backlog.0 += (speed - SPRAY_MIN_SPEED) * SPRAY_RATE * dt;
+            emit = backlog.0 as usize;
+            backlog.0 -= emit as f32;
+            bow = Some((bow_pos, ship_tf.rotation, ship_vel.linvel, speed));
+        } else {
+            backlog.0 = 0.0;
+        }
+    }
+
+    for (mut tf, mut visibility, mut particle) in particles.iter_mut() {
+        if particle.life > 0.0 {
+            particle.life -= dt;
+            particle.velocity.y -= 9.8 * dt;
+            tf.translation += particle.velocity * dt;
+            let fade = (particle.life / SPRAY_LIFETIME).clamp(0.0, 1.0);
+            tf.scale = Vec3::splat(0.04 + 0.08 * (1.0 - fade));
+            if particle.life <= 0.0 || tf.translation.y < ocean.sea_level - 1.0 {
+                particle.life = 0.0;
+                *visibility = Visibility::Hidden;
+            }
+        } else if emit > 0 {
+            let Some((bow_pos, rotation, hull_velocity, speed)) = bow else { continue };
+            emit -= 1;
+            let side = if rng.next() > 0.5 { 1.0 } else { -1.0 };
+            let local = Vec3::new(
+                side * (0.6 + rng.next() * 0.8),
+                1.2 + rng.next() * 1.5,
+                -0.3 - rng.next() * 0.5,
+            ) * (0.4 + speed * 0.12);
+            particle.velocity = hull_velocity + rotation * local;
+            particle.life = SPRAY_LIFETIME * (0.6 + rng.next() * 0.4);
+            tf.translation = bow_pos + rotation * Vec3::new(side * 0.4, 0.0, 0.0);
+            tf.scale = Vec3::splat(0.04);
+            *visibility = Visibility::Visible;
+        }
+    }
+}
@@ -0,0 +1,283 @@
+//! Weather and sea state – wind, waves, fog and sky blend between presets.
+//!
+//! Sea state rolls every few minutes (calm ↔ breeze ↔ squall ↔ storm). Carrying loot
+//! always worsens the next roll: the storm chases you home (proj.md).
+
+use bevy::prelude::*;
+
+use crate::artifacts::Inventory;
+use crate::game_state::GameState;
+use crate::ocean::{GerstnerWave, OceanSolver};
+use crate::sim_clock::SIM_DT;
+
+/// Seconds a change of sea state takes to fully blend in.
+const WEATHER_BLEND_DURATION: f32 = 45.0;
+
+/// Seconds between sea state rolls.
+const WEATHER_CHANGE_INTERVAL: f32 = 150.0;
+
+/// Max wind veer (radians) per sea state roll.
+const WIND_VEER_MAX: f32 = 0.6;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum SeaState {
+    #[default]
+    Calm,
+    Breeze,
+    Squall,
+    Storm,
+}
+
+impl SeaState {
+    fn worse(self) -> Self {
+        match self {
+            SeaState::Calm => SeaState::Breeze,
+            SeaState::Breeze => SeaState::Squall,
+            SeaState::Squall | SeaState::Storm => SeaState::Storm,
+        }
+    }
+
+    fn better(self) -> Self {
+        match self {
+            SeaState::Calm | SeaState::Breeze => SeaState::Calm,
+            SeaState::Squall => SeaState::Breeze,
+            SeaState::Storm => SeaState::Squall,
+        }
+    }
+
+    /// Preset values for this state. Calm matches the scene defaults in main.rs.
+    pub fn preset(self) -> WeatherParams {
+        match self {
+            SeaState::Calm => WeatherParams {
+                wind_speed: 3.0,
+                amplitude_scale: 1.0,
+                wavelength_scale: 1.0,
+                steepness_scale: 1.0,
+                fog_color: Color::srgba(0.5, 0.6, 0.8, 0.2),
+                fog_density: 0.008,
+                ambient_color: Color::srgb(0.65, 0.78, 0.95),
+                ambient_brightness: 500.0,
+                clear_color: Color::srgb(0.42, 0.6, 0.88),
+            },
+            SeaState::Breeze => WeatherParams {
+                wind_speed: 7.0,
+                amplitude_scale: 1.6,
+                wavelength_scale: 1.2,
+                steepness_scale: 1.1,
+                fog_color: Color::srgba(0.5, 0.58, 0.74, 0.22),
+                fog_density: 0.009,
+                ambient_color: Color::srgb(0.62, 0.72, 0.88),
+                ambient_brightness: 450.0,
+                clear_color: Color::srgb(0.45, 0.58, 0.8),
+            },
+            SeaState::Squall => WeatherParams {
+                wind_speed: 14.0,
+                amplitude_scale: 2.6,
+                wavelength_scale: 1.4,
+                steepness_scale: 1.2,
+                fog_color: Color::srgba(0.45, 0.5, 0.58, 0.3),
+                fog_density: 0.014,
+                ambient_color: Color::srgb(0.5, 0.56, 0.65),
+                ambient_brightness: 300.0,
+                clear_color: Color::srgb(0.35, 0.42, 0.52),
+            },
+            SeaState::Storm => WeatherParams {
+                wind_speed: 22.0,
+                amplitude_scale: 4.0,
+                wavelength_scale: 1.7,
+                steepness_scale: 1.25,
+                fog_color: Color::srgba(0.3, 0.34, 0.4, 0.45),
+                fog_density: 0.022,
+                ambient_color: Color::srgb(0.35, 0.4, 0.48),
+                ambient_brightness: 180.0,
+                clear_color: Color::srgb(0.2, 0.24, 0.3),
+            },
+        }
+    }
+}
+
+/// Blended weather output consumed by ocean, lighting and fog.
+#[derive(Clone, Copy)]
+pub struct WeatherParams {
+    /// Wind speed (m/s).
+    pub wind_speed: f32,
+    pub amplitude_scale: f32,
+    pub wavelength_scale: f32,
+    pub steepness_scale: f32,
+    pub fog_color: Color,
+    pub fog_density: f32,
+    pub ambient_color: Color,
+    pub ambient_brightness: f32,
+    pub clear_color: Color,
+}
+
+impl WeatherParams {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        let mix = |a: f32, b: f32| a + (b - a) * t;
+        Self {
+            wind_speed: mix(self.wind_speed, other.wind_speed),
+            amplitude_scale: mix(self.amplitude_scale, other.amplitude_scale),
+            wavelength_scale: mix(self.wavelength_scale, other.wavelength_scale),
+            steepness_scale: mix(self.steepness_scale, other.steepness_scale),
+            fog_color: self.fog_color.mix(&other.fog_color, t),
+            fog_density: mix(self.fog_density, other.fog_density),
+            ambient_color: self.ambient_color.mix(&other.ambient_color, t),
+            ambient_brightness: mix(self.ambient_brightness, other.ambient_brightness),
+            clear_color: self.clear_color.mix(&other.clear_color, t),
+        }
+    }
+}
+
+/// Current weather. `params` is the blend of the previous and target sea state.
+#[derive(Resource)]
+pub struct Weather {
+    pub state: SeaState,
+    previous: SeaState,
+    blend: f32,
+    /// Direction the wind blows toward (radians, XZ plane, 0 = +X).
+    pub wind_angle: f32,
+    previous_wind_angle: f32,
+    target_wind_angle: f32,
+    next_change: f32,
+    rolls: u32,
+    pub params: WeatherParams,
+}
+
+impl Default for Weather {
+    fn default() -> Self {
+        Self {
+            state: SeaState::Calm,
+            previous: SeaState::Calm,
+            blend: 1.0,
+            wind_angle: 0.2,
+            previous_wind_angle: 0.2,
+            target_wind_angle: 0.2,
+            next_change: WEATHER_CHANGE_INTERVAL,
+            rolls: 0,
+            params: SeaState::Calm.preset(),
+        }
+    }
+}
+
+impl Weather {
+    /// Wind velocity (m/s) in world space. Horizontal only.
+    pub fn wind(&self) -> Vec3 {
+        let dir = Vec2::from_angle(self.wind_angle);
+        Vec3::new(dir.x, 0.0, dir.y) * self.params.wind_speed
+    }
+
+    /// Start blending toward a sea state from the current one, even the same one: the wave
+    /// sets crossfade to the new wind heading `roll_weather` sets alongside.
+    pub fn set_state(&mut self, state: SeaState) {
+        self.previous = self.state;
+        self.state = state;
+        self.blend = 0.0;
+    }
+
+    /// Smoothstepped progress of the current blend, 0 = previous state, 1 = target.
+    fn blend_factor(&self) -> f32 {
+        self.blend * self.blend * (3.0 - 2.0 * self.blend)
+    }
+}
+
+pub struct WeatherPlugin;
+
+impl Plugin for WeatherPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Weather::default())
+            .add_systems(
+                FixedPreUpdate,
+                (roll_weather, blend_weather, apply_weather_to_ocean)
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                Update,
+                apply_weather_to_sky.run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+/// Deterministic hash → [0, 1) for weather rolls.
+fn roll_hash(n: u32) -> f32 {
+    let mut h = n.wrapping_mul(2654435761);
+    h = (h ^ (h >> 15)).wrapping_mul(2246822519);
+    h ^= h >> 13;
+    (h & 0xFFFF) as f32 / 65536.0
+}
+
+fn roll_weather(inventory: Res<Inventory>, mut weather: ResMut<Weather>) {
+    weather.next_change -= SIM_DT;
+    if weather.next_change > 0.0 {
+        return;
+    }
+    weather.next_change = WEATHER_CHANGE_INTERVAL;
+    weather.rolls += 1;
+
+    let roll = roll_hash(weather.rolls);
+    let next = if inventory.has_loot() || roll > 0.6 {
+        weather.state.worse()
+    } else if roll < 0.35 {
+        weather.state.better()
+    } else {
+        weather.state
+    };
+    weather.set_state(next);
+
+    let veer = (roll_hash(weather.rolls ^ 0x5bd1) * 2.0 - 1.0) * WIND_VEER_MAX;
+    weather.previous_wind_angle = weather.wind_angle;
+    weather.target_wind_angle = weather.wind_angle + veer;
+}
+
+fn blend_weather(mut weather: ResMut<Weather>) {
+    weather.blend = (weather.blend + SIM_DT / WEATHER_BLEND_DURATION).min(1.0);
+    let t = weather.blend_factor();
+    weather.params = weather.previous.preset().lerp(&weather.state.preset(), t);
+    weather.wind_angle =
+        weather.previous_wind_angle + (weather.target_wind_angle - weather.previous_wind_angle) * t;
+}
+
+/// Rebuilds the wave set from the preset's base swell: one copy per blended sea state, each
+/// scaled by its preset and turned into its own wind. Only amplitude crossfades, so every
+/// wave keeps its wavenumber, speed and heading and the phase never jumps mid-blend.
+fn apply_weather_to_ocean(weather: Res<Weather>, mut ocean: ResMut<OceanSolver>) {
+    let t = weather.blend_factor();
+    // The two sets are incoherent, so weight by sqrt to hold wave energy (mean square
+    // height) constant through the blend instead of flattening the sea midway.
+    let sets = [
+        (weather.previous.preset(), weather.previous_wind_angle, (1.0 - t).sqrt()),
+        (weather.state.preset(), weather.target_wind_angle, t.sqrt()),
+    ];
+    // Steepness is normalised by the total wave count, so scale it back up by the number
+    // of live sets to keep the horizontal displacement continuous as a set fades out.
+    let live = sets.iter().filter(|(_, _, weight)| *weight > 0.0).count() as f32;
+    let waves = sets
+        .iter()
+        .filter(|(_, _, weight)| *weight > 0.0)
+        .flat_map(|(p, wind_angle, weight)| {
+            let wind_rotation = Vec2::from_angle(*wind_angle);
+            ocean.base_waves.iter().map(move |wave| {
+                GerstnerWave::new(
+                    wave.wavelength * p.wavelength_scale,
+                    wave.amplitude * p.amplitude_scale * weight,
+                    // Deep water: phase speed grows with sqrt(wavelength).
+                    wave.speed * p.wavelength_scale.sqrt(),
+                    wind_rotation.rotate(wave.direction),
+                    (wave.steepness * p.steepness_scale).min(1.0) * weight * live,
+                )
+            })
+        })
+        .collect();
+    ocean.waves = waves;
+}
+
+fn apply_weather_to_sky(
+    weather: Res<Weather>,
+    mut clear_color: ResMut<ClearColor>,
+    mut ambient: ResMut<AmbientLight>,
+) {
+    let p = &weather.params;
+    clear_color.0 = p.clear_color;
+    ambient.color = p.ambient_color;
+    ambient.brightness = p.ambient_brightness;
+}
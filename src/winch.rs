@@ -2,17 +2,22 @@
 //!
 //! The cable constrains the sub to stay within max distance of the ship.
 //! R / T to reel in/out when in boat. Visual cable drawn between anchors.
+//! Tension is read back from the joint impulse each step: it hauls on the stern, shows on
+//! the winch gauge and slows the reel; past the breaking strain the cable parts.
+//! A cut cable leaves the sub free; R at the helm with the sub alongside rigs a new one.
 
 use bevy::prelude::*;
 
 use bevy_rapier3d::prelude::*;
 use crate::artifacts::{Artifact, AttachedArtifact, Inventory};
 use crate::audio::ArtifactPickupEvent;
-use crate::diving_bell::Submersible;
+use crate::diving_bell::{Submersible, SUB_HEIGHT};
 use crate::game_state::GameState;
+use crate::hud::{spawn_hud_row, WINCH_SLOT};
 use crate::settings::InputBindings;
 use crate::player::PlayerMode;
-use crate::ship::Ship;
+use crate::ship::{Ship, HULL_HALF_EXTENTS, SHIP_SCALE};
+use crate::sim_clock::SIM_DT;
 
 /// Max cable length (m). Sub cannot go further than this from the ship.
 const MAX_CABLE_LENGTH: f32 = 100.0;
@@ -23,15 +28,49 @@ const MIN_CABLE_LENGTH: f32 = 5.0;
 /// Reel speed (m/sec).
 const REEL_SPEED: f32 = 8.0;
 
-/// Winch attachment on ship (local space): stern, above deck.
-const SHIP_ANCHOR: Vec3 = Vec3::new(0.0, 0.6, 2.5);
+/// Winch attachment on ship (local space): stern, at deck level. Cable tension acts here,
+/// so a heavy load squats the stern.
+const SHIP_ANCHOR: Vec3 = Vec3::new(0.0, HULL_HALF_EXTENTS.y * SHIP_SCALE, 9.0);
 
 /// Winch attachment on sub (local space): top center.
-const SUB_ANCHOR: Vec3 = Vec3::new(0.0, 2.5, 0.0);
+const SUB_ANCHOR: Vec3 = Vec3::new(0.0, SUB_HEIGHT * 0.5, 0.0);
+
+/// Sub within this distance (m) of the winch can have a new cable rigged.
+const RIG_RANGE: f32 = 20.0;
+
+/// Cable tension (N) at which it parts.
+const BREAKING_STRAIN: f32 = 1.5e5;
+
+/// Tension (N) at which the reel stalls hauling in.
+const REEL_STALL_TENSION: f32 = 1.2e5;
+
+/// Share of each step's tension reading taken into the gauge, smoothing out solver jitter.
+const TENSION_SMOOTHING: f32 = 0.2;
 
 #[derive(Resource)]
 pub struct WinchState {
     pub cable_length: f32,
+    /// Cable runs from the ship to the sub. False once cut.
+    pub tethered: bool,
+    /// Cable tension (N), smoothed. Zero while slack or cut.
+    pub tension: f32,
+}
+
+/// Whether the cable was rigged, from a loaded save.
+#[derive(Resource, Default)]
+pub struct RestoreTether(pub Option<bool>);
+
+/// Cuts the cable: the sub is free of the ship until a new one is rigged.
+pub fn cut_cable(commands: &mut Commands, winch: &mut WinchState, sub_id: Entity) {
+    commands.entity(sub_id).remove::<ImpulseJoint>();
+    winch.tethered = false;
+    winch.tension = 0.0;
+}
+
+fn rope(length: f32) -> RopeJointBuilder {
+    RopeJointBuilder::new(length)
+        .local_anchor1(SHIP_ANCHOR)
+        .local_anchor2(SUB_ANCHOR)
 }
 
 /// Cable mesh entity – updated each frame to span ship anchor to sub anchor.
@@ -41,23 +80,39 @@ struct CableVisual(Entity);
 #[derive(Component)]
 struct CableMesh;
 
+#[derive(Component)]
+struct WinchUiText;
+
+#[derive(Resource)]
+struct WinchUiRoot(Entity);
+
 pub struct WinchPlugin;
 
 impl Plugin for WinchPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(WinchState {
             cable_length: MAX_CABLE_LENGTH,
+            tethered: true,
+            tension: 0.0,
         })
-        .add_systems(Startup, (spawn_winch_joint, spawn_cable_visual))
+        .init_resource::<RestoreTether>()
+        .add_systems(Startup, (spawn_winch_joint, spawn_cable_visual, spawn_winch_ui))
+        .add_systems(
+            FixedUpdate,
+            cable_tension.run_if(in_state(GameState::Playing)),
+        )
         .add_systems(
             Update,
             (
-                winch_controls
+                (winch_controls, rig_cable)
                     .run_if(in_state(GameState::Playing))
                     .run_if(|mode: Res<PlayerMode>| mode.in_boat),
-                update_winch_joint_length.run_if(in_state(GameState::Playing)),
+                (restore_tether, update_winch_joint_length)
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
                 update_cable_visual.run_if(in_state(GameState::Playing)),
                 deliver_attached_artifact.run_if(in_state(GameState::Playing)),
+                update_winch_ui.run_if(in_state(GameState::Playing)),
             ),
         );
     }
@@ -72,12 +127,59 @@ fn spawn_winch_joint(
     let Ok(ship_id) = ship_query.single() else { return };
     let Ok(sub_id) = sub_query.single() else { return };
 
-    let rope = RopeJointBuilder::new(winch.cable_length)
-        .local_anchor1(SHIP_ANCHOR)
-        .local_anchor2(SUB_ANCHOR);
-    let joint = ImpulseJoint::new(ship_id, rope);
+    commands
+        .entity(sub_id)
+        .insert(ImpulseJoint::new(ship_id, rope(winch.cable_length)));
+}
 
-    commands.entity(sub_id).insert(joint);
+/// Rigs or cuts the cable to match a loaded save, at the saved length.
+fn restore_tether(
+    mut commands: Commands,
+    mut restore: ResMut<RestoreTether>,
+    mut winch: ResMut<WinchState>,
+    ship_query: Query<Entity, With<Ship>>,
+    sub_query: Query<Entity, With<Submersible>>,
+) {
+    let Some(tethered) = restore.0.take() else { return };
+    let Ok(ship_id) = ship_query.single() else { return };
+    let Ok(sub_id) = sub_query.single() else { return };
+    if tethered {
+        winch.tethered = true;
+        winch.tension = 0.0;
+        commands
+            .entity(sub_id)
+            .insert(ImpulseJoint::new(ship_id, rope(winch.cable_length)));
+    } else {
+        cut_cable(&mut commands, &mut winch, sub_id);
+    }
+}
+
+/// R with no cable and the sub alongside: shackle on a new one at the current distance.
+fn rig_cable(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<InputBindings>,
+    mut winch: ResMut<WinchState>,
+    ship_query: Query<(Entity, &Transform), With<Ship>>,
+    sub_query: Query<(Entity, &Transform), With<Submersible>>,
+) {
+    if winch.tethered || !keyboard.just_pressed(bindings.reel_in) {
+        return;
+    }
+    let Ok((ship_id, ship_tf)) = ship_query.single() else { return };
+    let Ok((sub_id, sub_tf)) = sub_query.single() else { return };
+    let from = ship_tf.translation + ship_tf.rotation * SHIP_ANCHOR;
+    let to = sub_tf.translation + sub_tf.rotation * SUB_ANCHOR;
+    let distance = from.distance(to);
+    if distance > RIG_RANGE {
+        return;
+    }
+    winch.cable_length = distance.clamp(MIN_CABLE_LENGTH, MAX_CABLE_LENGTH);
+    winch.tethered = true;
+    commands
+        .entity(sub_id)
+        .insert(ImpulseJoint::new(ship_id, rope(winch.cable_length)));
+    bevy::log::info!("Winch cable rigged to the sub");
 }
 
 fn winch_controls(
@@ -86,12 +188,17 @@ fn winch_controls(
     mut winch: ResMut<WinchState>,
     time: Res<Time>,
 ) {
+    if !winch.tethered {
+        return;
+    }
     let reel_in = keyboard.pressed(bindings.reel_in);
     let reel_out = keyboard.pressed(bindings.reel_out);
     let delta = time.delta_secs() * REEL_SPEED;
 
     if reel_in && !reel_out {
-        winch.cable_length = (winch.cable_length - delta).max(MIN_CABLE_LENGTH);
+        // The motor labours under load and stalls short of the breaking strain.
+        let haul = (1.0 - winch.tension / REEL_STALL_TENSION).clamp(0.0, 1.0);
+        winch.cable_length = (winch.cable_length - delta * haul).max(MIN_CABLE_LENGTH);
     } else if reel_out && !reel_in {
         winch.cable_length = (winch.cable_length + delta).min(MAX_CABLE_LENGTH);
     }
@@ -118,6 +225,7 @@ fn spawn_cable_visual(
             Mesh3d(cable_mesh),
             MeshMaterial3d(cable_mat),
             Transform::default(),
+            Visibility::default(),
             CableMesh,
         ))
         .id();
@@ -126,25 +234,98 @@ fn spawn_cable_visual(
 
 fn update_cable_visual(
     cable: Res<CableVisual>,
+    winch: Res<WinchState>,
     ship_query: Query<&Transform, With<Ship>>,
     sub_query: Query<&Transform, With<Submersible>>,
-    mut transform_query: Query<&mut Transform, (With<CableMesh>, Without<Submersible>, Without<Ship>)>,
+    mut transform_query: Query<
+        (&mut Transform, &mut Visibility),
+        (With<CableMesh>, Without<Submersible>, Without<Ship>),
+    >,
 ) {
     let Ok(ship_tf) = ship_query.single() else { return };
     let Ok(sub_tf) = sub_query.single() else { return };
+    let Ok((mut cable_tf, mut visibility)) = transform_query.get_mut(cable.0) else { return };
+    if !winch.tethered {
+        *visibility = Visibility::Hidden;
+        return;
+    }
+    *visibility = Visibility::Inherited;
 
     let from = ship_tf.translation + ship_tf.rotation * SHIP_ANCHOR;
     let to = sub_tf.translation + sub_tf.rotation * SUB_ANCHOR;
     let delta = to - from;
     let len = delta.length().max(0.1);
 
-    let Ok(mut cable_tf) = transform_query.get_mut(cable.0) else { return };
-
     cable_tf.translation = (from + to) * 0.5;
     cable_tf.scale = Vec3::new(1.0, len * 0.5, 1.0);
     cable_tf.rotation = Quat::from_rotation_arc(Vec3::Y, delta.normalize());
 }
 
+/// Reads the rope's constraint impulse from the last physics step as tension, and parts the
+/// cable past its breaking strain.
+fn cable_tension(
+    mut commands: Commands,
+    rapier_context: ReadRapierContext,
+    mut winch: ResMut<WinchState>,
+    sub_query: Query<Entity, (With<Submersible>, With<ImpulseJoint>)>,
+) {
+    if !winch.tethered {
+        return;
+    }
+    let Ok(sub_id) = sub_query.single() else { return };
+    let Ok(context) = rapier_context.single() else { return };
+    let impulse = context
+        .joints
+        .entity2impulse_joint()
+        .get(&sub_id)
+        .and_then(|handle| context.joints.impulse_joints.get(*handle))
+        .map_or(0.0, |joint| joint.impulses.fixed_rows::<3>(0).norm());
+    let tension = winch.tension + (impulse / SIM_DT - winch.tension) * TENSION_SMOOTHING;
+    winch.tension = tension;
+    if tension > BREAKING_STRAIN {
+        cut_cable(&mut commands, &mut winch, sub_id);
+        bevy::log::warn!("The winch cable parted at {:.0} kN", tension / 1000.0);
+    }
+}
+
+fn spawn_winch_ui(mut commands: Commands) {
+    let (root_id, _) = spawn_hud_row(&mut commands, WINCH_SLOT, WinchUiText);
+    commands.insert_resource(WinchUiRoot(root_id));
+}
+
+/// Cable out and tension against the breaking strain, turning red near it.
+fn update_winch_ui(
+    mode: Res<PlayerMode>,
+    winch: Res<WinchState>,
+    ui: Res<WinchUiRoot>,
+    mut visibility_query: Query<&mut Visibility>,
+    mut text_query: Query<(&mut Text, &mut TextColor), With<WinchUiText>>,
+) {
+    let Ok(mut root_vis) = visibility_query.get_mut(ui.0) else { return };
+    if !mode.in_vehicle() {
+        *root_vis = Visibility::Hidden;
+        return;
+    }
+    *root_vis = Visibility::Visible;
+    let Ok((mut text, mut color)) = text_query.single_mut() else { return };
+    if !winch.tethered {
+        *text = Text::new("Winch: no cable  [R] rig with the sub alongside");
+        color.0 = Color::srgba(1.0, 1.0, 1.0, 0.95);
+        return;
+    }
+    *text = Text::new(format!(
+        "Cable {:.0} m  Tension {:.0} / {:.0} kN  [R/T] reel",
+        winch.cable_length,
+        winch.tension / 1000.0,
+        BREAKING_STRAIN / 1000.0,
+    ));
+    color.0 = if winch.tension > BREAKING_STRAIN * 0.8 {
+        Color::srgba(1.0, 0.35, 0.3, 0.95)
+    } else {
+        Color::srgba(1.0, 1.0, 1.0, 0.95)
+    };
+}
+
 /// When cable is reeled to min length with an artifact attached, deliver to inventory.
 fn deliver_attached_artifact(
     winch: Res<WinchState>,
@@ -154,7 +335,7 @@ fn deliver_attached_artifact(
     artifact_query: Query<&Artifact>,
     mut commands: Commands,
 ) {
-    if winch.cable_length > MIN_CABLE_LENGTH + 0.5 {
+    if !winch.tethered || winch.cable_length > MIN_CABLE_LENGTH + 0.5 {
         return;
     }
     let Some(art_id) = attached.0 else { return };
@@ -0,0 +1,311 @@
+//! Sinking and wrecks – the ship's failure state.
+//!
+//! Swamped past `FOUNDER_FLOOD`, or floating upside down for `CAPSIZE_TIME`, the ship
+//! founders: the crew is thrown clear, the winch cable parts and she goes down. She settles
+//! on the seabed as a `Wreck` with her cargo and planks strewn around it, ready to be salvaged
+//! (pickups, or the winch for heavy artifacts). Wrecks and what's left of their cargo are
+//! saved. A replacement boat waits at Safe Island with starting stores.
+
+use std::f32::consts::TAU;
+
+use bevy::gltf::GltfAssetLabel;
+use bevy::prelude::*;
+use bevy::scene::SceneRoot;
+use bevy_rapier3d::prelude::*;
+
+use crate::anchor::{Anchor, Moored};
+use crate::artifacts::{is_heavy, Artifact, Inventory, REPAIR_WOOD};
+use crate::autopilot::Autopilot;
+use crate::character::MarineCharacter;
+use crate::diving_bell::Submersible;
+use crate::game_state::GameState;
+use crate::interaction::{Interactable, InteractKind};
+use crate::player::{put_camera_on_character, PlayerCamera, PlayerMode, VEHICLE_ENTER_RANGE};
+use crate::sailing::Sails;
+use crate::save_load::WreckSave;
+use crate::ship::{ship_spawn_transform, HullVolume, HULL_HALF_EXTENTS, SHIP_SCALE};
+use crate::ship_hull::{flood_hull, ShipHull};
+use crate::ship_stores::ShipStores;
+use crate::sim_clock::SIM_DT;
+use crate::winch::{cut_cable, WinchState};
+use crate::world::MAP_FLOOR_Y;
+
+/// Fraction of the hull flooded at which she founders.
+const FOUNDER_FLOOD: f32 = 0.7;
+
+/// Ship's up vector below this (world Y) counts as capsized.
+const CAPSIZE_UP: f32 = -0.2;
+
+/// Seconds capsized before she's lost.
+const CAPSIZE_TIME: f32 = 8.0;
+
+/// Seconds from foundering until she's on the bottom and the wreck is laid.
+const FOUNDER_TIME: f32 = 12.0;
+
+/// List (rad) the wreck settles at.
+const WRECK_LIST: f32 = 0.35;
+
+/// Cargo is strewn from this far (m) from the wreck outward.
+const DEBRIS_RADIUS: f32 = 6.0;
+
+/// Deepest seabed (m below the surface) searched for when laying the wreck.
+const MAX_WRECK_DEPTH: f32 = 300.0;
+
+/// A lost ship on the seabed.
+#[derive(Component)]
+pub struct Wreck;
+
+/// Cargo lying around a wreck, still to be salvaged.
+#[derive(Component)]
+pub struct WreckCargo {
+    pub wreck: Entity,
+    pub item_id: String,
+}
+
+/// On the ship while she goes down.
+#[derive(Component)]
+pub struct Foundering {
+    elapsed: f32,
+}
+
+/// Wrecks from a loaded save, replacing those in the world.
+#[derive(Resource, Default)]
+pub struct RestoreWrecks(pub Option<Vec<WreckSave>>);
+
+#[derive(Resource)]
+struct WreckAssets {
+    scene: Handle<Scene>,
+    crate_mesh: Handle<Mesh>,
+    heavy_mesh: Handle<Mesh>,
+    plank_mesh: Handle<Mesh>,
+    crate_mat: Handle<StandardMaterial>,
+    plank_mat: Handle<StandardMaterial>,
+}
+
+pub struct WreckPlugin;
+
+impl Plugin for WreckPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RestoreWrecks>()
+            .add_systems(Startup, load_wreck_assets)
+            .add_systems(
+                FixedUpdate,
+                (check_foundering, go_down)
+                    .chain()
+                    .after(flood_hull)
+                    .before(PhysicsSet::SyncBackend)
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                Update,
+                (abandon_ship, restore_wrecks).run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+fn load_wreck_assets(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.insert_resource(WreckAssets {
+        scene: asset_server.load(GltfAssetLabel::Scene(0).from_asset("models/boat-row-small.glb")),
+        crate_mesh: meshes.add(Cuboid::new(0.5, 0.5, 0.8)),
+        heavy_mesh: meshes.add(Cuboid::new(1.0, 1.0, 1.2)),
+        plank_mesh: meshes.add(Cuboid::new(1.6, 0.12, 0.3)),
+        crate_mat: materials.add(StandardMaterial {
+            base_color: Color::srgb(0.5, 0.4, 0.25),
+            metallic: 0.5,
+            perceptual_roughness: 0.7,
+            ..default()
+        }),
+        plank_mat: materials.add(StandardMaterial {
+            base_color: Color::srgb(0.4, 0.3, 0.18),
+            perceptual_roughness: 0.95,
+            ..default()
+        }),
+    });
+}
+
+fn check_foundering(
+    mut commands: Commands,
+    mut capsized_for: Local<f32>,
+    query: Query<(Entity, &Transform, &ShipHull, &HullVolume), Without<Foundering>>,
+) {
+    for (ship_id, transform, hull, hull_volume) in query.iter() {
+        *capsized_for = if transform.up().y < CAPSIZE_UP {
+            *capsized_for + SIM_DT
+        } else {
+            0.0
+        };
+        let swamped = hull.flooded >= FOUNDER_FLOOD * hull_volume.volume();
+        if swamped || *capsized_for >= CAPSIZE_TIME {
+            *capsized_for = 0.0;
+            commands.entity(ship_id).insert(Foundering { elapsed: 0.0 });
+            bevy::log::warn!("The ship is going down!");
+        }
+    }
+}
+
+/// Throws the helmsman clear, parts the winch cable, slips anchor and moorings.
+fn abandon_ship(
+    mut commands: Commands,
+    mut mode: ResMut<PlayerMode>,
+    mut winch: ResMut<WinchState>,
+    mut ship_query: Query<(&Transform, &mut Autopilot), Added<Foundering>>,
+    camera_query: Query<Entity, With<PlayerCamera>>,
+    character_query: Query<Entity, With<MarineCharacter>>,
+    sub_query: Query<Entity, With<Submersible>>,
+    anchor_query: Query<Entity, With<Anchor>>,
+    moored_query: Query<Entity, With<Moored>>,
+) {
+    let Ok((ship_tf, mut autopilot)) = ship_query.single_mut() else { return };
+    autopilot.disengage();
+    if mode.in_boat {
+        mode.in_boat = false;
+        if let (Some(cam_id), Some(char_id)) =
+            (camera_query.iter().next(), character_query.iter().next())
+        {
+            commands
+                .entity(char_id)
+                .insert(Transform::from_translation(ship_tf.translation + Vec3::Y * 3.0));
+            put_camera_on_character(&mut commands, cam_id, char_id);
+        }
+    }
+    if winch.tethered {
+        if let Ok(sub_id) = sub_query.single() {
+            cut_cable(&mut commands, &mut winch, sub_id);
+        }
+    }
+    for anchor_id in anchor_query.iter() {
+        commands.entity(anchor_id).despawn();
+    }
+    for point_id in moored_query.iter() {
+        commands.entity(point_id).remove::<(ImpulseJoint, Moored)>();
+    }
+}
+
+/// Fills her to the deck until she's on the bottom, then lays the wreck and readies the
+/// replacement boat at Safe Island.
+fn go_down(
+    mut commands: Commands,
+    assets: Res<WreckAssets>,
+    rapier_context: ReadRapierContext,
+    mut inventory: ResMut<Inventory>,
+    mut query: Query<(
+        Entity,
+        &mut Transform,
+        &mut Velocity,
+        &HullVolume,
+        &mut ShipHull,
+        &mut ShipStores,
+        &mut Sails,
+        &mut Foundering,
+    )>,
+) {
+    for (ship_id, mut transform, mut velocity, hull_volume, mut hull, mut stores, mut sails, mut foundering) in
+        query.iter_mut()
+    {
+        hull.flooded = hull_volume.volume();
+        foundering.elapsed += SIM_DT;
+        if foundering.elapsed < FOUNDER_TIME {
+            continue;
+        }
+
+        let from = Vec3::new(transform.translation.x, 0.0, transform.translation.z);
+        let bed = rapier_context
+            .single()
+            .ok()
+            .and_then(|context| {
+                context.cast_ray(from, Vec3::NEG_Y, MAX_WRECK_DEPTH, true, QueryFilter::only_fixed())
+            })
+            .map_or(MAP_FLOOR_Y, |(_, toi)| from.y - toi);
+        let (yaw, _, _) = transform.rotation.to_euler(EulerRot::YXZ);
+        let mut cargo = std::mem::take(&mut inventory.items);
+        cargo.extend((0..stores.repair_wood).map(|_| REPAIR_WOOD.to_string()));
+        let save = WreckSave {
+            translation: [from.x, bed + HULL_HALF_EXTENTS.y * SHIP_SCALE * 0.6, from.z],
+            rotation: Quat::from_euler(EulerRot::YXZ, yaw, 0.0, WRECK_LIST).to_array(),
+            cargo,
+        };
+        lay_wreck(&mut commands, &assets, &save);
+        bevy::log::warn!(
+            "The ship is lost in {:.0} m of water. A replacement waits at Safe Island.",
+            -bed
+        );
+
+        *transform = ship_spawn_transform();
+        *velocity = Velocity::zero();
+        hull.integrity = hull.max_integrity;
+        hull.flooded = 0.0;
+        *stores = ShipStores::starting();
+        sails.hoisted = 0.0;
+        commands.entity(ship_id).remove::<Foundering>();
+    }
+}
+
+/// Spawns a wreck on the seabed with its cargo strewn around it.
+fn lay_wreck(commands: &mut Commands, assets: &WreckAssets, save: &WreckSave) {
+    let translation = Vec3::from_array(save.translation);
+    let wreck_id = commands
+        .spawn((
+            SceneRoot(assets.scene.clone()),
+            Transform::from_translation(translation)
+                .with_rotation(Quat::from_array(save.rotation))
+                .with_scale(Vec3::splat(SHIP_SCALE)),
+            RigidBody::Fixed,
+            Collider::cuboid(HULL_HALF_EXTENTS.x, HULL_HALF_EXTENTS.y, HULL_HALF_EXTENTS.z),
+            Wreck,
+        ))
+        .id();
+
+    for (i, item_id) in save.cargo.iter().enumerate() {
+        // Golden-angle spiral: spread out, no two pieces on top of each other.
+        let angle = i as f32 * TAU * 0.382;
+        let radius = DEBRIS_RADIUS + i as f32 * 0.8;
+        let position = Vec3::new(
+            translation.x + angle.cos() * radius,
+            translation.y - HULL_HALF_EXTENTS.y * SHIP_SCALE * 0.4,
+            translation.z + angle.sin() * radius,
+        );
+        let (mesh, material, half, kind) = if item_id == REPAIR_WOOD {
+            let kind = InteractKind::Pickup { item_id: item_id.clone() };
+            (&assets.plank_mesh, &assets.plank_mat, Vec3::new(0.8, 0.06, 0.15), kind)
+        } else if is_heavy(item_id) {
+            let kind = InteractKind::AttachToWinch { item_id: item_id.clone() };
+            (&assets.heavy_mesh, &assets.crate_mat, Vec3::new(0.5, 0.5, 0.6), kind)
+        } else {
+            let kind = InteractKind::Pickup { item_id: item_id.clone() };
+            (&assets.crate_mesh, &assets.crate_mat, Vec3::new(0.25, 0.25, 0.4), kind)
+        };
+        let mut piece = commands.spawn((
+            Mesh3d(mesh.clone()),
+            MeshMaterial3d(material.clone()),
+            Transform::from_translation(position).with_rotation(Quat::from_rotation_y(angle)),
+            RigidBody::Fixed,
+            Collider::cuboid(half.x, half.y, half.z),
+            Interactable { kind, range: VEHICLE_ENTER_RANGE },
+            WreckCargo { wreck: wreck_id, item_id: item_id.clone() },
+        ));
+        if item_id != REPAIR_WOOD {
+            piece.insert(Artifact { item_id: item_id.clone() });
+        }
+    }
+}
+
+/// Swaps the world's wrecks for those in a loaded save.
+fn restore_wrecks(
+    mut commands: Commands,
+    mut restore: ResMut<RestoreWrecks>,
+    assets: Res<WreckAssets>,
+    existing: Query<Entity, Or<(With<Wreck>, With<WreckCargo>)>>,
+) {
+    let Some(wrecks) = restore.0.take() else { return };
+    for entity in existing.iter() {
+        commands.entity(entity).despawn();
+    }
+    for save in &wrecks {
+        lay_wreck(&mut commands, &assets, save);
+    }
+}